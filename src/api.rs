@@ -0,0 +1,1035 @@
+//! Requests to the GitHub REST and GraphQL APIs.
+
+use reqwest::Client;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::GitStatError;
+use crate::stats::sort_chronologically;
+use crate::models::{
+    ContributionBreakdown, ContributionCalendar, ContributionDay, ContributionWeek,
+    ContributionsCollection, DateRange, GitHubUser, LanguageStat, RateLimit, RepoContributions,
+};
+
+// Structure de la réponse de /rate_limit (REST)
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    resources: RateLimitResources,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResources {
+    core: RateLimit,
+}
+
+// Structures pour le quota GraphQL
+#[derive(Debug, Deserialize)]
+struct RateLimitData {
+    #[serde(rename = "rateLimit")]
+    rate_limit: Option<GraphQlRateLimit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlRateLimit {
+    limit: u32,
+    remaining: u32,
+    #[serde(rename = "resetAt")]
+    reset_at: chrono::DateTime<chrono::Utc>,
+}
+
+// Structures pour l'authentification en tant que GitHub App (REST)
+#[derive(Serialize)]
+struct AppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Installation {
+    id: u64,
+    account: Option<InstallationAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstallationAccount {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstallationToken {
+    token: String,
+}
+
+// Structures pour l'activité des organisations (REST)
+#[derive(Debug, Deserialize)]
+struct OrgRepository {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrgMember {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitActivityWeek {
+    // Unix timestamp of the Sunday starting the week
+    week: i64,
+    days: [u32; 7],
+}
+
+// Structures pour la requête GraphQL
+#[derive(Serialize)]
+struct GraphQLRequest<V> {
+    query: String,
+    variables: V,
+}
+
+#[derive(Serialize)]
+struct GraphQLVariables {
+    username: String,
+    from: Option<String>,
+    to: Option<String>,
+}
+
+// Structures pour la réponse GraphQL
+#[derive(Debug, Deserialize)]
+struct GraphQLResponse<T> {
+    data: Option<T>,
+    errors: Option<Vec<GraphQLError>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLData {
+    user: Option<GitHubUserWithContributions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubUserWithContributions {
+    #[allow(dead_code)]
+    login: String,
+    #[allow(dead_code)]
+    name: Option<String>,
+    #[serde(rename = "contributionsCollection")]
+    contributions_collection: ContributionsCollection,
+}
+
+// Structures pour la requête des langages
+#[derive(Serialize)]
+struct LanguagesVariables {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguagesData {
+    user: Option<UserRepositories>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserRepositories {
+    repositories: RepositoryConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryConnection {
+    nodes: Vec<Option<RepositoryLanguages>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryLanguages {
+    languages: Option<LanguageConnection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageConnection {
+    edges: Vec<LanguageEdge>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageEdge {
+    size: u64,
+    node: Language,
+}
+
+#[derive(Debug, Deserialize)]
+struct Language {
+    name: String,
+    color: Option<String>,
+}
+
+// Structures pour la requête des étoiles
+#[derive(Serialize)]
+struct StarsVariables {
+    username: String,
+    cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StarsData {
+    user: Option<UserStarredRepositories>,
+    #[serde(rename = "rateLimit")]
+    rate_limit: Option<GraphQlRateLimit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserStarredRepositories {
+    repositories: StarredRepositoryConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct StarredRepositoryConnection {
+    nodes: Vec<Option<RepositoryStars>>,
+    #[serde(rename = "pageInfo")]
+    page_info: PageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryStars {
+    #[serde(rename = "stargazerCount")]
+    stargazer_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageInfo {
+    #[serde(rename = "hasNextPage")]
+    has_next_page: bool,
+    #[serde(rename = "endCursor")]
+    end_cursor: Option<String>,
+}
+
+// Structures pour la requête des contributions par dépôt
+#[derive(Debug, Deserialize)]
+struct RepoContributionsData {
+    user: Option<UserCommitsByRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserCommitsByRepository {
+    #[serde(rename = "contributionsCollection")]
+    contributions_collection: CommitsByRepositoryCollection,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitsByRepositoryCollection {
+    #[serde(rename = "commitContributionsByRepository")]
+    commit_contributions_by_repository: Vec<RepositoryCommits>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryCommits {
+    repository: Option<RepositoryName>,
+    contributions: ContributionCount,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryName {
+    #[serde(rename = "nameWithOwner")]
+    name_with_owner: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContributionCount {
+    #[serde(rename = "totalCount")]
+    total_count: u32,
+}
+
+/// Organizations have no contribution calendar, so theirs is built from the
+/// commit activity of their most recently pushed repositories
+pub const ORG_REPO_LIMIT: usize = 30;
+
+/// GraphQL points `--stars` leaves untouched; paging stops with a rate-limit
+/// error rather than dip below them
+pub const STARS_QUOTA_RESERVE: u32 = 50;
+
+/// Number of languages shown by `--languages`
+pub const TOP_LANGUAGES: usize = 6;
+
+/// Number of repositories shown by `--by-repo`
+pub const TOP_REPOSITORIES: usize = 8;
+
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Requests allowed in flight at once unless `--concurrency` says otherwise
+pub const DEFAULT_CONCURRENCY: u16 = 2;
+
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// HTTP client plus the settings shared by every API call
+pub struct Api {
+    pub client: Client,
+    // REST API root without a trailing slash
+    pub base_url: String,
+    pub retries: u32,
+    pub timeout_secs: u64,
+    // Every request holds a permit while it is sent, which keeps concurrent
+    // fetches clear of GitHub's secondary rate limits
+    pub limiter: Semaphore,
+    // Pause after each request before its permit goes to the next one
+    pub delay_ms: u64,
+}
+
+/// Checks that the token is accepted and, for classic tokens, that it carries
+/// the read:user scope. Fine-grained tokens don't report scopes, so only the
+/// authentication itself is checked for them.
+pub async fn validate_token(api: &Api, token: &str) -> Result<(), GitStatError> {
+    let request = api.client
+        .get(format!("{}/user", api.base_url))
+        .bearer_auth(token)
+        .header("User-Agent", "gitstat-cli");
+    let response = send_with_retry(api, request).await?;
+    
+    check_rate_limit(&response)?;
+    
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(GitStatError::Unauthorized("GitHub token is invalid or expired".to_string()));
+    }
+    if !response.status().is_success() {
+        return Err(GitStatError::Http(response.status()));
+    }
+    
+    if let Some(scopes) = response.headers().get("x-oauth-scopes").and_then(|v| v.to_str().ok()) {
+        let has_scope = scopes.split(',').map(str::trim).any(|s| s == "read:user" || s == "user");
+        if !has_scope {
+            return Err(GitStatError::Unauthorized("GitHub token is missing the 'read:user' scope".to_string()));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Mints an installation token for a GitHub App, to be sent like any other token.
+/// The app authenticates with a short-lived JWT signed by its private key; the
+/// installation used is the app's only one, or else the one on `account`.
+pub async fn get_installation_token(
+    api: &Api,
+    app_id: u64,
+    key: &jsonwebtoken::EncodingKey,
+    account: &str,
+) -> Result<String, GitStatError> {
+    // Backdated a minute against clock drift; GitHub refuses expiries over ten minutes away
+    let now = chrono::Utc::now().timestamp();
+    let claims = AppClaims { iat: now - 60, exp: now + 540, iss: app_id.to_string() };
+    let jwt = jsonwebtoken::encode(&jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256), &claims, key)
+        .map_err(|e| GitStatError::Unauthorized(format!("could not sign the GitHub App JWT: {}", e)))?;
+    
+    let request = api.client
+        .get(format!("{}/app/installations?per_page=100", api.base_url))
+        .bearer_auth(&jwt)
+        .header("User-Agent", "gitstat-cli");
+    let response = send_with_retry(api, request).await?;
+    
+    check_rate_limit(&response)?;
+    
+    let installations: Vec<Installation> = match response.status() {
+        status if status.is_success() => read_json(response).await?,
+        reqwest::StatusCode::UNAUTHORIZED => {
+            return Err(GitStatError::Unauthorized(format!("GitHub App {} rejected its JWT, check the app ID and private key", app_id)));
+        }
+        status => return Err(GitStatError::Http(status)),
+    };
+    let installation = match installations.as_slice() {
+        [only] => only,
+        _ => installations
+            .iter()
+            .find(|i| i.account.as_ref().is_some_and(|a| a.login.eq_ignore_ascii_case(account)))
+            .ok_or_else(|| GitStatError::NotFound { kind: "GitHub App installation for", name: account.to_string() })?,
+    };
+    log::debug!("using installation {} of GitHub App {}", installation.id, app_id);
+    
+    let request = api.client
+        .post(format!("{}/app/installations/{}/access_tokens", api.base_url, installation.id))
+        .bearer_auth(&jwt)
+        .header("User-Agent", "gitstat-cli");
+    let response = send_with_retry(api, request).await?;
+    
+    check_rate_limit(&response)?;
+    
+    match response.status() {
+        status if status.is_success() => Ok(read_json::<InstallationToken>(response).await?.token),
+        status => Err(GitStatError::Http(status)),
+    }
+}
+
+/// Returns the token's REST quota. Checking it doesn't count against it.
+/// GitHub Enterprise servers without rate limiting answer 404, giving `None`.
+pub async fn get_rate_limit(api: &Api, token: &str) -> Result<Option<RateLimit>, GitStatError> {
+    let request = api.client
+        .get(format!("{}/rate_limit", api.base_url))
+        .bearer_auth(token)
+        .header("User-Agent", "gitstat-cli");
+    let response = send_with_retry(api, request).await?;
+    
+    match response.status() {
+        status if status.is_success() => {
+            let body: RateLimitResponse = read_json(response).await?;
+            Ok(Some(body.resources.core))
+        }
+        reqwest::StatusCode::NOT_FOUND => Ok(None),
+        status => Err(GitStatError::Http(status)),
+    }
+}
+
+/// Returns the token's GraphQL quota, which the contribution queries draw
+/// from. Servers that don't rate limit GraphQL report no quota, giving `None`.
+pub async fn get_graphql_rate_limit(api: &Api, token: &str) -> Result<Option<RateLimit>, GitStatError> {
+    let query = "query { rateLimit { limit remaining resetAt } }";
+    let (data, errors): (RateLimitData, _) = post_graphql(api, token, query, serde_json::json!({})).await?;
+    for message in &errors {
+        log::debug!("ignoring partial GraphQL error for rateLimit: {}", message);
+    }
+    Ok(data.rate_limit.map(|quota| RateLimit {
+        limit: quota.limit,
+        remaining: quota.remaining,
+        reset: quota.reset_at.timestamp(),
+    }))
+}
+
+/// Fetches a user's public profile from the REST API. An empty token sends
+/// the request anonymously.
+pub async fn get_user_info(api: &Api, username: &str, token: &str) -> Result<GitHubUser, GitStatError> {
+    let url = format!("{}/users/{}", api.base_url, username);
+    let mut request = api.client
+        .get(&url)
+        .header("User-Agent", "gitstat-cli");
+    if !token.is_empty() {
+        request = request.bearer_auth(token);
+    }
+    let response = send_with_retry(api, request).await?;
+    
+    check_rate_limit(&response)?;
+    
+    match response.status() {
+        status if status.is_success() => read_json(response).await,
+        reqwest::StatusCode::NOT_FOUND => Err(GitStatError::NotFound { kind: "User", name: username.to_string() }),
+        reqwest::StatusCode::UNAUTHORIZED if !token.is_empty() => {
+            Err(GitStatError::Unauthorized(format!("GitHub refused the credentials used to look up user '{}'", username)))
+        }
+        // An exhausted quota was caught above; Retry-After marks a secondary rate limit
+        reqwest::StatusCode::FORBIDDEN => match response.headers().get("retry-after").and_then(|v| v.to_str().ok()?.parse::<i64>().ok()) {
+            Some(seconds) => Err(GitStatError::RateLimited { reset_in_minutes: Some((seconds + 59) / 60) }),
+            // Without credentials there is nothing to blame the 403 on
+            None if token.is_empty() => Err(GitStatError::Http(reqwest::StatusCode::FORBIDDEN)),
+            None => Err(GitStatError::Unauthorized(format!("access to user '{}' is forbidden (HTTP 403)", username))),
+        },
+        status => Err(GitStatError::Http(status)),
+    }
+}
+
+pub async fn get_org_info(api: &Api, org: &str, token: &str) -> Result<GitHubUser, GitStatError> {
+    let url = format!("{}/orgs/{}", api.base_url, org);
+    let request = api.client
+        .get(&url)
+        .bearer_auth(token)
+        .header("User-Agent", "gitstat-cli");
+    let response = send_with_retry(api, request).await?;
+    
+    check_rate_limit(&response)?;
+    
+    match response.status() {
+        status if status.is_success() => read_json(response).await,
+        reqwest::StatusCode::NOT_FOUND => Err(GitStatError::NotFound { kind: "Organization", name: org.to_string() }),
+        status => Err(GitStatError::Http(status)),
+    }
+}
+
+// Members listed per page of /orgs/{org}/public_members, GitHub's maximum
+const MEMBERS_PER_PAGE: usize = 100;
+
+/// Logins of an organization's public members, every page of them.
+/// Private memberships aren't listed, whatever the token can see.
+pub async fn get_org_members(api: &Api, org: &str, token: &str) -> Result<Vec<String>, GitStatError> {
+    let mut members = Vec::new();
+    for page in 1.. {
+        let url = format!("{}/orgs/{}/public_members?per_page={}&page={}", api.base_url, org, MEMBERS_PER_PAGE, page);
+        let request = api.client
+            .get(&url)
+            .bearer_auth(token)
+            .header("User-Agent", "gitstat-cli");
+        let response = send_with_retry(api, request).await?;
+        
+        check_rate_limit(&response)?;
+        
+        let batch: Vec<OrgMember> = match response.status() {
+            status if status.is_success() => read_json(response).await?,
+            reqwest::StatusCode::NOT_FOUND => return Err(GitStatError::NotFound { kind: "Organization", name: org.to_string() }),
+            status => return Err(GitStatError::Http(status)),
+        };
+        let last_page = batch.len() < MEMBERS_PER_PAGE;
+        members.extend(batch.into_iter().map(|member| member.login));
+        if last_page {
+            break;
+        }
+    }
+    Ok(members)
+}
+
+/// Builds an approximate contribution calendar for an organization by summing
+/// the last year of default-branch commit activity of its most recently pushed
+/// repositories
+pub async fn get_org_contributions(
+    api: &Api,
+    org: &str,
+    token: &str,
+) -> Result<ContributionsCollection, GitStatError> {
+    let url = format!("{}/orgs/{}/repos?sort=pushed&per_page={}", api.base_url, org, ORG_REPO_LIMIT);
+    let request = api.client
+        .get(&url)
+        .bearer_auth(token)
+        .header("User-Agent", "gitstat-cli");
+    let response = send_with_retry(api, request).await?;
+    
+    check_rate_limit(&response)?;
+    
+    if !response.status().is_success() {
+        return Err(GitStatError::Http(response.status()));
+    }
+    let repos: Vec<OrgRepository> = read_json(response).await?;
+    
+    // Polled all at once; the limiter keeps the requests within --concurrency
+    let activities = futures::future::join_all(repos.iter().map(|repo| get_commit_activity(api, org, &repo.name, token))).await;
+    let mut weeks: std::collections::BTreeMap<i64, [u32; 7]> = std::collections::BTreeMap::new();
+    for (repo, activity) in repos.iter().zip(activities) {
+        let activity = match activity {
+            Ok(Some(activity)) => activity,
+            // Statistics GitHub is still computing
+            Ok(None) => {
+                log::debug!("commit activity of {}/{} is not ready yet, leaving it out", org, repo.name);
+                continue;
+            }
+            // Every other repository would fail the same way
+            Err(e @ GitStatError::RateLimited { .. }) => return Err(e),
+            Err(e) => {
+                log::warn!("leaving {}/{} out of the organization's activity: {}", org, repo.name, e);
+                continue;
+            }
+        };
+        for week in activity {
+            let days = weeks.entry(week.week).or_insert([0; 7]);
+            for (total, count) in days.iter_mut().zip(week.days) {
+                *total += count;
+            }
+        }
+    }
+    Ok(activity_contributions(weeks))
+}
+
+/// Builds the contribution calendar of a single repository from the last year
+/// of its default-branch commit activity
+pub async fn get_repository_activity(
+    api: &Api,
+    owner: &str,
+    repo: &str,
+    token: &str,
+) -> Result<ContributionsCollection, GitStatError> {
+    let name = format!("{}/{}", owner, repo);
+    let activity = get_commit_activity(api, owner, repo, token)
+        .await?
+        .ok_or(GitStatError::StatsPending(name))?;
+    Ok(activity_contributions(activity.into_iter().map(|week| (week.week, week.days)).collect()))
+}
+
+// Daily commit counts keyed by the Unix timestamp of each week's Sunday, as a
+// calendar that ends today
+fn activity_contributions(weeks: std::collections::BTreeMap<i64, [u32; 7]>) -> ContributionsCollection {
+    let today = chrono::Utc::now().date_naive();
+    let mut calendar = ContributionCalendar {
+        total_contributions: 0,
+        weeks: Vec::new(),
+    };
+    for (week_start, counts) in weeks {
+        let Some(start) = chrono::DateTime::from_timestamp(week_start, 0) else { continue };
+        let contribution_days: Vec<ContributionDay> = counts.iter()
+            .enumerate()
+            .map(|(offset, &count)| (start.date_naive() + chrono::Duration::days(offset as i64), count))
+            .filter(|(date, _)| *date <= today)
+            .map(|(date, count)| ContributionDay {
+                date: date.format("%Y-%m-%d").to_string(),
+                contribution_count: count,
+                color: String::new(),
+            })
+            .collect();
+        calendar.total_contributions += contribution_days.iter().map(|d| d.contribution_count).sum::<u32>();
+        calendar.weeks.push(ContributionWeek { contribution_days });
+    }
+    
+    ContributionsCollection {
+        breakdown: ContributionBreakdown {
+            commits: calendar.total_contributions,
+            ..Default::default()
+        },
+        contribution_calendar: calendar,
+        restricted_contributions: 0,
+    }
+}
+
+// Polls of a repository's statistics while GitHub computes them, and the wait between two
+const STATS_ATTEMPTS: u32 = 3;
+const STATS_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+// GitHub computes repository statistics lazily and answers 202 while it does,
+// so poll a few times; None when they still aren't ready
+async fn get_commit_activity(
+    api: &Api,
+    owner: &str,
+    repo: &str,
+    token: &str,
+) -> Result<Option<Vec<CommitActivityWeek>>, GitStatError> {
+    let url = format!("{}/repos/{}/{}/stats/commit_activity", api.base_url, owner, repo);
+    for _ in 0..STATS_ATTEMPTS {
+        let request = api.client
+            .get(&url)
+            .bearer_auth(token)
+            .header("User-Agent", "gitstat-cli");
+        let response = send_with_retry(api, request).await?;
+        
+        check_rate_limit(&response)?;
+        
+        match response.status() {
+            reqwest::StatusCode::ACCEPTED => {
+                tokio::time::sleep(STATS_RETRY_DELAY).await;
+            }
+            // Empty repository
+            reqwest::StatusCode::NO_CONTENT => return Ok(Some(Vec::new())),
+            status if status.is_success() => return read_json(response).await.map(Some),
+            reqwest::StatusCode::NOT_FOUND => {
+                return Err(GitStatError::NotFound { kind: "Repository", name: format!("{}/{}", owner, repo) })
+            }
+            status => return Err(GitStatError::Http(status)),
+        }
+    }
+    Ok(None)
+}
+
+// Calendar and per-type totals of one user, over `from`..`to` (the last year when unset)
+const CONTRIBUTIONS_QUERY: &str = r#"
+query($username: String!, $from: DateTime, $to: DateTime) {
+    user(login: $username) {
+        login
+        name
+        contributionsCollection(from: $from, to: $to) {
+            totalCommitContributions
+            totalPullRequestContributions
+            totalIssueContributions
+            totalPullRequestReviewContributions
+            restrictedContributionsCount
+            contributionCalendar {
+                totalContributions
+                weeks {
+                    contributionDays {
+                        date
+                        contributionCount
+                        color
+                    }
+                }
+            }
+        }
+    }
+}
+"#;
+
+/// Fetches a user's contribution calendar and per-type totals over GraphQL,
+/// for the last year or for the given `period`.
+pub async fn get_user_contributions_real(
+    api: &Api,
+    username: &str,
+    token: &str,
+    period: Option<DateRange>,
+) -> Result<ContributionsCollection, GitStatError> {
+    let variables = contributions_variables(username, period);
+    
+    let (data, errors): (GraphQLData, _) = post_graphql(api, token, CONTRIBUTIONS_QUERY, variables).await?;
+    let mut user = resolved_user(data.user, errors, username)?;
+    
+    if sort_chronologically(&mut user.contributions_collection.contribution_calendar) {
+        log::debug!("contribution calendar for {} was not in chronological order, sorted it", username);
+    }
+    Ok(user.contributions_collection)
+}
+
+/// Lists the repositories that received the most commits from the user over
+/// the same period as the calendar. Repositories the token cannot access come
+/// back without a name and are left out.
+pub async fn get_repo_contributions(
+    api: &Api,
+    username: &str,
+    token: &str,
+    period: Option<DateRange>,
+) -> Result<Vec<RepoContributions>, GitStatError> {
+    let query = r#"
+        query($username: String!, $from: DateTime, $to: DateTime) {
+            user(login: $username) {
+                contributionsCollection(from: $from, to: $to) {
+                    commitContributionsByRepository(maxRepositories: 25) {
+                        repository {
+                            nameWithOwner
+                        }
+                        contributions {
+                            totalCount
+                        }
+                    }
+                }
+            }
+        }
+    "#;
+    
+    let variables = contributions_variables(username, period);
+    let (data, errors): (RepoContributionsData, _) = post_graphql(api, token, query, variables).await?;
+    let user = resolved_user(data.user, errors, username)?;
+    
+    let mut repos: Vec<RepoContributions> = user.contributions_collection
+        .commit_contributions_by_repository
+        .into_iter()
+        .filter_map(|entry| {
+            entry.repository.map(|repository| RepoContributions {
+                name: repository.name_with_owner,
+                commits: entry.contributions.total_count,
+            })
+        })
+        .collect();
+    repos.sort_by_key(|r| std::cmp::Reverse(r.commits));
+    repos.truncate(TOP_REPOSITORIES);
+    
+    Ok(repos)
+}
+
+/// The request `get_user_contributions_real` sends for `username`, written out
+/// for --print-query. The token only travels in the Authorization header.
+pub fn describe_contributions_request(base_url: &str, username: &str, period: Option<DateRange>) -> String {
+    let variables = serde_json::to_string_pretty(&contributions_variables(username, period)).unwrap_or_default();
+    format!(
+        "POST {}\nAuthorization: bearer <token, not shown>\n\nQuery:\n{}\n\nVariables:\n{}",
+        graphql_url(base_url),
+        CONTRIBUTIONS_QUERY.trim_matches('\n'),
+        variables
+    )
+}
+
+// `from` and `to` are DateTimes, so the range covers whole days in UTC
+fn contributions_variables(username: &str, period: Option<DateRange>) -> GraphQLVariables {
+    GraphQLVariables {
+        username: username.to_string(),
+        from: period.map(|p| format!("{}T00:00:00Z", p.from)),
+        to: period.map(|p| format!("{}T23:59:59Z", p.to)),
+    }
+}
+
+// GitHub Enterprise serves REST under /api/v3 and GraphQL under /api/graphql,
+// while github.com uses api.github.com and api.github.com/graphql
+fn graphql_url(base_url: &str) -> String {
+    match base_url.strip_suffix("/v3") {
+        Some(api_root) => format!("{}/graphql", api_root),
+        None => format!("{}/graphql", base_url),
+    }
+}
+
+// Posts a GraphQL query and returns its `data` along with any error messages.
+// GitHub can return both when only some fields failed, so errors are only
+// fatal on their own when no data came back at all.
+async fn post_graphql<V: Serialize, T: DeserializeOwned>(
+    api: &Api,
+    token: &str,
+    query: &str,
+    variables: V,
+) -> Result<(T, Vec<String>), GitStatError> {
+    let request = GraphQLRequest {
+        query: query.to_string(),
+        variables,
+    };
+    
+    let request = api.client
+        .post(graphql_url(&api.base_url))
+        .bearer_auth(token)
+        .header("User-Agent", "gitstat-cli")
+        .json(&request);
+    let response = send_with_retry(api, request).await?;
+    
+    check_rate_limit(&response)?;
+    
+    if !response.status().is_success() {
+        return Err(GitStatError::Http(response.status()));
+    }
+    
+    let graphql_response: GraphQLResponse<T> = read_json(response).await?;
+    
+    let error_messages: Vec<String> = graphql_response.errors
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| e.message)
+        .collect();
+    
+    match graphql_response.data {
+        Some(data) => Ok((data, error_messages)),
+        None if error_messages.is_empty() => Err(GitStatError::GraphQl("No data returned by API".to_string())),
+        None => Err(GitStatError::GraphQl(error_messages.join(", "))),
+    }
+}
+
+// The `user` root of a query. Errors next to a resolved user only concern
+// some fields and are just logged; without a user they explain why.
+fn resolved_user<U>(user: Option<U>, errors: Vec<String>, username: &str) -> Result<U, GitStatError> {
+    match user {
+        Some(user) => {
+            for message in &errors {
+                log::debug!("ignoring partial GraphQL error for {}: {}", username, message);
+            }
+            Ok(user)
+        }
+        None if errors.is_empty() => Err(GitStatError::NotFound { kind: "User", name: username.to_string() }),
+        None => Err(GitStatError::GraphQl(errors.join(", "))),
+    }
+}
+
+/// Aggregates language byte counts over the user's own (non-fork) repositories.
+/// Only the first 100 repositories are considered; repositories the token
+/// cannot see are simply not returned by the API.
+pub async fn get_top_languages(
+    api: &Api,
+    username: &str,
+    token: &str,
+) -> Result<Vec<LanguageStat>, GitStatError> {
+    let query = r#"
+        query($username: String!) {
+            user(login: $username) {
+                repositories(first: 100, ownerAffiliations: OWNER, isFork: false) {
+                    nodes {
+                        languages(first: 10, orderBy: {field: SIZE, direction: DESC}) {
+                            edges {
+                                size
+                                node {
+                                    name
+                                    color
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    "#;
+    
+    let variables = LanguagesVariables {
+        username: username.to_string(),
+    };
+    let (data, errors): (LanguagesData, _) = post_graphql(api, token, query, variables).await?;
+    let user = resolved_user(data.user, errors, username)?;
+    
+    let mut totals: Vec<LanguageStat> = Vec::new();
+    let edges = user.repositories.nodes.into_iter()
+        .flatten()
+        .filter_map(|repo| repo.languages)
+        .flat_map(|languages| languages.edges);
+    for edge in edges {
+        match totals.iter_mut().find(|l| l.name == edge.node.name) {
+            Some(stat) => stat.bytes += edge.size,
+            None => totals.push(LanguageStat {
+                name: edge.node.name,
+                color: edge.node.color,
+                bytes: edge.size,
+                percent: 0.0,
+            }),
+        }
+    }
+    
+    let total_bytes: u64 = totals.iter().map(|l| l.bytes).sum();
+    if total_bytes > 0 {
+        for stat in &mut totals {
+            stat.percent = stat.bytes as f64 * 100.0 / total_bytes as f64;
+        }
+    }
+    totals.sort_by_key(|l| std::cmp::Reverse(l.bytes));
+    totals.truncate(TOP_LANGUAGES);
+    
+    Ok(totals)
+}
+
+/// Sums the stargazers of the user's public repositories, 100 repositories
+/// per request. Every page reports the remaining GraphQL quota, so a user
+/// with thousands of repositories can't drain it.
+pub async fn get_total_stars(api: &Api, username: &str, token: &str) -> Result<u64, GitStatError> {
+    let query = r#"
+        query($username: String!, $cursor: String) {
+            user(login: $username) {
+                repositories(first: 100, after: $cursor, ownerAffiliations: OWNER, privacy: PUBLIC) {
+                    nodes {
+                        stargazerCount
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+            }
+            rateLimit {
+                remaining
+                limit
+                resetAt
+            }
+        }
+    "#;
+    
+    let mut total = 0;
+    let mut cursor = None;
+    loop {
+        let variables = StarsVariables {
+            username: username.to_string(),
+            cursor,
+        };
+        let (data, errors): (StarsData, _) = post_graphql(api, token, query, variables).await?;
+        let user = resolved_user(data.user, errors, username)?;
+        let repositories = user.repositories;
+        total += repositories.nodes.iter().flatten().map(|repo| repo.stargazer_count).sum::<u64>();
+        
+        cursor = match repositories.page_info.end_cursor {
+            Some(next) if repositories.page_info.has_next_page => Some(next),
+            _ => return Ok(total),
+        };
+        if let Some(quota) = data.rate_limit {
+            log::debug!("{} stars so far, {} GraphQL points left", total, quota.remaining);
+            if quota.remaining < STARS_QUOTA_RESERVE {
+                let seconds = (quota.reset_at - chrono::Utc::now()).num_seconds().max(0);
+                return Err(GitStatError::RateLimited { reset_in_minutes: Some((seconds + 59) / 60) });
+            }
+        }
+    }
+}
+
+// Sends the request, retrying connection errors, timeouts and 5xx responses
+// with exponential backoff (500ms, 1s, 2s, ...). 4xx responses are returned
+// immediately since retrying them won't help.
+async fn send_with_retry(api: &Api, request: reqwest::RequestBuilder) -> Result<reqwest::Response, GitStatError> {
+    let describe = |e: reqwest::Error| {
+        if e.is_timeout() {
+            GitStatError::Timeout(api.timeout_secs)
+        } else {
+            GitStatError::Network(e)
+        }
+    };
+    
+    let mut attempt = 0;
+    loop {
+        let Some(current) = request.try_clone() else {
+            // Streaming bodies can't be replayed, so send once
+            return send_limited(api, request).await.map_err(describe);
+        };
+        
+        let result = send_limited(api, current).await;
+        let transient = match &result {
+            Ok(response) => response.status().is_server_error(),
+            Err(e) => e.is_connect() || e.is_timeout(),
+        };
+        
+        if !transient || attempt >= api.retries {
+            return result.map_err(describe);
+        }
+        
+        let delay = 500 << attempt.min(6);
+        log::debug!("retrying in {}ms (attempt {} of {})", delay, attempt + 1, api.retries);
+        tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+        attempt += 1;
+    }
+}
+
+// Waits for a free slot under --concurrency, then sends the request and
+// spaces it from the next one by --delay-ms
+async fn send_limited(api: &Api, request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+    // The semaphore is never closed, so acquiring can't fail
+    let _permit = api.limiter.acquire().await.ok();
+    let result = send_logged(request).await;
+    if api.delay_ms > 0 {
+        tokio::time::sleep(std::time::Duration::from_millis(api.delay_ms)).await;
+    }
+    result
+}
+
+// Sends a single request, logging the URL, status and rate-limit headers.
+// The Authorization header is never written out.
+async fn send_logged(request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+    let (client, request) = request.build_split();
+    let request = request?;
+    let method = request.method().clone();
+    let url = request.url().clone();
+    
+    log::debug!("{} {}", method, url);
+    for (name, value) in request.headers() {
+        // `bearer_auth` marks the token as sensitive, which also keeps it out
+        // of the request's Debug output
+        let value = if value.is_sensitive() || name == reqwest::header::AUTHORIZATION {
+            "[redacted]"
+        } else {
+            value.to_str().unwrap_or("[binary]")
+        };
+        log::trace!("> {}: {}", name, value);
+    }
+    
+    let started = Instant::now();
+    let response = match client.execute(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::debug!("{} {} failed after {} ms: {}", method, url, started.elapsed().as_millis(), e);
+            return Err(e);
+        }
+    };
+    
+    let header = |name: &str| {
+        response.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("-")
+            .to_string()
+    };
+    log::debug!("{} {} -> {} in {} ms (rate limit: {}/{} remaining, resets at {})",
+        method,
+        url,
+        response.status(),
+        started.elapsed().as_millis(),
+        header("x-ratelimit-remaining"),
+        header("x-ratelimit-limit"),
+        header("x-ratelimit-reset")
+    );
+    Ok(response)
+}
+
+// Downloads and parses a JSON body, logging its size once decompressed and
+// how long the transfer took on top of the response headers
+async fn read_json<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, GitStatError> {
+    let url = response.url().clone();
+    let started = Instant::now();
+    let body = response.bytes().await?;
+    log::debug!("{} <- {} bytes in {} ms", url, body.len(), started.elapsed().as_millis());
+    Ok(serde_json::from_slice(&body)?)
+}
+
+// Turns an exhausted quota into an explicit error instead of a bare 403. Only a
+// 429, or a 403 with X-RateLimit-Remaining at 0, becomes `RateLimited`; any other
+// response, including a success that used up the quota, is left to the caller.
+fn check_rate_limit(response: &reqwest::Response) -> Result<(), GitStatError> {
+    let header = |name: &str| {
+        response.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i64>().ok())
+    };
+    
+    let limited = match response.status() {
+        reqwest::StatusCode::TOO_MANY_REQUESTS => true,
+        reqwest::StatusCode::FORBIDDEN => header("x-ratelimit-remaining") == Some(0),
+        _ => false,
+    };
+    if !limited {
+        return Ok(());
+    }
+    
+    let reset_in_minutes = header("x-ratelimit-reset")
+        .map(|reset| (reset - chrono::Utc::now().timestamp()).max(0))
+        .or_else(|| header("retry-after"))
+        .map(|seconds| (seconds + 59) / 60);
+    Err(GitStatError::RateLimited { reset_in_minutes })
+}
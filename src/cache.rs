@@ -0,0 +1,139 @@
+//! On-disk cache of API responses.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::api::{get_org_contributions, get_org_info, get_user_contributions_real, get_user_info, Api, DEFAULT_API_URL};
+use crate::error::GitStatError;
+use crate::models::{ContributionsCollection, DateRange, GitHubUser};
+use crate::paths;
+use crate::stats::whole_year;
+
+/// Cached API responses older than this are refetched
+pub const CACHE_TTL_SECS: i64 = 60 * 60;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    Use,
+    Refresh,
+    Off,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<T> {
+    fetched_at: i64,
+    data: T,
+}
+
+pub async fn load_user_info(
+    api: &Api,
+    username: &str,
+    org: bool,
+    token: &str,
+    cache_mode: CacheMode,
+) -> Result<GitHubUser, GitStatError> {
+    let kind = if org { "org" } else { "user" };
+    let key = cache_key(api, format!("{}-{}", kind, username.to_lowercase()));
+    
+    if cache_mode == CacheMode::Use {
+        if let Some(user) = read_cache(&key) {
+            return Ok(user);
+        }
+    }
+    
+    let user = if org {
+        get_org_info(api, username, token).await?
+    } else {
+        get_user_info(api, username, token).await?
+    };
+    if cache_mode != CacheMode::Off {
+        write_cache(&key, &user);
+    }
+    Ok(user)
+}
+
+pub async fn load_user_contributions(
+    api: &Api,
+    username: &str,
+    token: &str,
+    period: Option<DateRange>,
+    org: bool,
+    cache_mode: CacheMode,
+) -> Result<ContributionsCollection, GitStatError> {
+    let kind = if org { "org-contributions" } else { "contributions" };
+    let key = cache_key(api, format!("{}-{}-{}", kind, username.to_lowercase(), period_key(period)));
+    
+    if cache_mode == CacheMode::Use {
+        if let Some(contributions) = read_cache(&key) {
+            return Ok(contributions);
+        }
+    }
+    
+    let contributions = if org {
+        get_org_contributions(api, username, token).await?
+    } else {
+        get_user_contributions_real(api, username, token, period).await?
+    };
+    if cache_mode != CacheMode::Off {
+        write_cache(&key, &contributions);
+    }
+    Ok(contributions)
+}
+
+// "last" for the default window, the year for a whole calendar year,
+// otherwise both dates
+fn period_key(period: Option<DateRange>) -> String {
+    match period {
+        None => "last".to_string(),
+        Some(range) => match whole_year(range) {
+            Some(year) => year.to_string(),
+            None => format!("{}_{}", range.from, range.to),
+        },
+    }
+}
+
+// Entries from other GitHub hosts are namespaced so they never collide
+fn cache_key(api: &Api, key: String) -> String {
+    if api.base_url == DEFAULT_API_URL {
+        return key;
+    }
+    let host: String = api.base_url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '_' })
+        .collect();
+    format!("{}-{}", host, key)
+}
+
+// Returns the cached value for `key` if present and fresher than CACHE_TTL_SECS.
+// Any I/O or parse failure is treated as a cache miss.
+fn read_cache<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let path = paths::cache_dir()?.join(format!("{}.json", key));
+    let contents = fs::read_to_string(path).ok()?;
+    let entry: CacheEntry<T> = serde_json::from_str(&contents).ok()?;
+    
+    let age = chrono::Utc::now().timestamp() - entry.fetched_at;
+    if (0..CACHE_TTL_SECS).contains(&age) {
+        Some(entry.data)
+    } else {
+        None
+    }
+}
+
+// Best effort: a cache that can't be written should never fail the command
+fn write_cache<T: Serialize>(key: &str, data: &T) {
+    let Some(dir) = paths::cache_dir() else { return };
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    
+    let entry = CacheEntry {
+        fetched_at: chrono::Utc::now().timestamp(),
+        data,
+    };
+    if let Ok(contents) = serde_json::to_string(&entry) {
+        let _ = fs::write(dir.join(format!("{}.json", key)), contents);
+    }
+}
@@ -0,0 +1,138 @@
+//! Defaults loaded from the TOML config file.
+
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::export::OutputFormat;
+use crate::paths;
+use crate::render::Theme;
+
+/// Defaults read from the config file; command line flags take precedence
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub token: Option<String>,
+    pub theme: Option<Theme>,
+    pub format: Option<OutputFormat>,
+    pub timeout: Option<u64>,
+}
+
+// Every key Config accepts, to point typos at the one that was meant
+const CONFIG_KEYS: [&str; 4] = ["token", "theme", "format", "timeout"];
+
+/// config.toml in the platform's config directory, e.g. ~/.config/gitstat/config.toml
+pub fn default_config_path() -> Option<PathBuf> {
+    paths::config_dir().map(|dir| dir.join("config.toml"))
+}
+
+/// A missing default config file is not an error, but an explicit --config
+/// path that doesn't exist is (it's most likely a typo)
+pub fn load_config(path: Option<&std::path::Path>) -> Result<Config, Box<dyn std::error::Error>> {
+    let (path, required) = match path {
+        Some(path) => (path.to_path_buf(), true),
+        None => match default_config_path() {
+            Some(path) => (path, false),
+            None => return Ok(Config::default()),
+        },
+    };
+    
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound && !required => return Ok(Config::default()),
+        Err(e) => return Err(format!("could not read config file {}: {}", path.display(), e).into()),
+    };
+    
+    let invalid = |reason: String| format!("invalid config file {}: {}", path.display(), reason);
+    let table: toml::Table = toml::from_str(&contents).map_err(|e| invalid(e.to_string()))?;
+    // Checked before deserializing so a misspelled key gets a suggestion
+    // rather than serde's list of every expected field
+    for key in table.keys() {
+        if !CONFIG_KEYS.contains(&key.as_str()) {
+            let reason = match suggest_key(key) {
+                Some(known) => format!("unknown config key '{}' (did you mean '{}'?)", key, known),
+                None => format!("unknown config key '{}' (expected one of: {})", key, CONFIG_KEYS.join(", ")),
+            };
+            return Err(invalid(reason).into());
+        }
+    }
+    
+    Config::deserialize(table).map_err(|e| invalid(e.to_string()).into())
+}
+
+// The closest known key, if it is near enough to be a plausible typo
+fn suggest_key(key: &str) -> Option<&'static str> {
+    let key = key.to_lowercase();
+    CONFIG_KEYS
+        .iter()
+        .map(|known| (levenshtein(&key, known), *known))
+        .filter(|(distance, known)| *distance <= known.len() / 3 + 1)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+// Edit distance counting insertions, deletions and substitutions, one row at a time
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn load(name: &str, contents: &str) -> Result<Config, String> {
+        let path = std::env::temp_dir().join(format!("gitstat-config-{}-{}.toml", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        let config = load_config(Some(&path)).map_err(|e| e.to_string());
+        let _ = fs::remove_file(&path);
+        config
+    }
+    
+    #[test]
+    fn edit_distance() {
+        assert_eq!(levenshtein("theme", "theme"), 0);
+        assert_eq!(levenshtein("themme", "theme"), 1);
+        assert_eq!(levenshtein("fromat", "format"), 2);
+        assert_eq!(levenshtein("", "token"), 5);
+    }
+    
+    #[test]
+    fn typos_get_a_suggestion() {
+        assert_eq!(suggest_key("themme"), Some("theme"));
+        assert_eq!(suggest_key("tokn"), Some("token"));
+        assert_eq!(suggest_key("fromat"), Some("format"));
+        assert_eq!(suggest_key("Timeout"), Some("timeout"));
+    }
+    
+    #[test]
+    fn distant_keys_get_no_suggestion() {
+        assert_eq!(suggest_key("colour"), None);
+        assert_eq!(suggest_key("proxy"), None);
+    }
+    
+    #[test]
+    fn unknown_key_error_names_the_suggestion() {
+        let error = load("typo", "themme = \"nord\"\n").unwrap_err();
+        assert!(error.ends_with("unknown config key 'themme' (did you mean 'theme'?)"), "{}", error);
+        let error = load("unrelated", "colour = \"blue\"\n").unwrap_err();
+        assert!(error.ends_with("unknown config key 'colour' (expected one of: token, theme, format, timeout)"), "{}", error);
+    }
+    
+    #[test]
+    fn known_keys_are_accepted() {
+        let config = load("valid", "theme = \"nord\"\ntimeout = 5\n").unwrap();
+        assert_eq!(config.theme, Some(Theme::Nord));
+        assert_eq!(config.timeout, Some(5));
+    }
+}
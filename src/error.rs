@@ -0,0 +1,49 @@
+//! Errors returned when talking to the GitHub API.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GitStatError {
+    /// The user or organization doesn't exist (or isn't visible to the token)
+    #[error("{kind} '{name}' not found")]
+    NotFound { kind: &'static str, name: String },
+    
+    /// The token was rejected or lacks a required scope
+    #[error("{0}")]
+    Unauthorized(String),
+    
+    /// The API quota is exhausted; `reset_in_minutes` comes from X-RateLimit-Reset
+    #[error("GitHub API rate limit exceeded{}", reset_hint(*.reset_in_minutes))]
+    RateLimited { reset_in_minutes: Option<i64> },
+    
+    /// The request didn't complete within the configured timeout
+    #[error("request timed out after {0} seconds")]
+    Timeout(u64),
+    
+    /// Connection failures and unreadable responses
+    #[error(transparent)]
+    Network(#[from] reqwest::Error),
+    
+    /// A response body that isn't the JSON we expected
+    #[error("unexpected response from GitHub: {0}")]
+    Decode(#[from] serde_json::Error),
+    
+    /// Any other unsuccessful HTTP status
+    #[error("HTTP error: {0}")]
+    Http(reqwest::StatusCode),
+    
+    /// GitHub was still computing a repository's statistics after several polls
+    #[error("GitHub is still computing the statistics of '{0}'")]
+    StatsPending(String),
+    
+    /// Errors reported in a GraphQL response body
+    #[error("GraphQL errors: {0}")]
+    GraphQl(String),
+}
+
+fn reset_hint(reset_in_minutes: Option<i64>) -> String {
+    match reset_in_minutes {
+        Some(minutes) => format!(", resets in {} minute(s)", minutes),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,520 @@
+//! Non-terminal outputs: JSON, CSV/TSV, summary line, markdown, Prometheus metrics,
+//! SVG, PNG and iCalendar.
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+
+use crate::models::{
+    ContributionBreakdown, ContributionCalendar, ContributionsCollection, GitHubUser, LanguageStat,
+    RepoContributions,
+};
+use crate::render::{cell_level, month_labels, period_label, Palette, RenderOptions, LEVEL_ASCII_CHARS};
+use crate::stats::{
+    average_per_active_day, best_day, compute_streaks, consistency_percent, count_active_days,
+    daily_std_dev, intensity_level, longest_gap,
+    max_contributions_per_day, sort_chronologically, week_month, MONTHS,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Colored terminal rendering
+    Terminal,
+    /// Single JSON document on stdout
+    Json,
+    /// One CSV row per contribution day
+    Csv,
+    /// The CSV rows, separated by tabs
+    Tsv,
+    /// Single parseable summary line
+    Summary,
+    /// Statistics table and ASCII calendar for a README
+    Markdown,
+    /// Prometheus text exposition metrics, labeled with the username
+    Prometheus,
+}
+
+/// Combined document emitted by `--format json`.
+///
+/// Fields are serialized in declaration order, which is kept stable: the
+/// profile, the raw calendar, the data fetched alongside it, and finally the
+/// statistics computed from the calendar. New fields are only ever appended.
+#[derive(Serialize)]
+pub struct UserReport<'a> {
+    pub login: &'a str,
+    pub name: Option<&'a str>,
+    pub public_repos: u32,
+    pub followers: u32,
+    pub following: u32,
+    pub calendar: &'a ContributionCalendar,
+    pub breakdown: &'a ContributionBreakdown,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub languages: Option<&'a [LanguageStat]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repositories: Option<&'a [RepoContributions]>,
+    pub statistics: ReportStatistics,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stars: Option<u64>,
+}
+
+/// A `--format json` document read back by `--from-file`. The statistics
+/// are recomputed from the calendar, so they aren't read.
+#[derive(Deserialize)]
+pub struct SavedReport {
+    pub login: String,
+    pub name: Option<String>,
+    pub public_repos: u32,
+    pub followers: u32,
+    pub following: u32,
+    pub calendar: ContributionCalendar,
+    pub breakdown: ContributionBreakdown,
+    #[serde(default)]
+    pub languages: Option<Vec<LanguageStat>>,
+    #[serde(default)]
+    pub repositories: Option<Vec<RepoContributions>>,
+    #[serde(default)]
+    pub stars: Option<u64>,
+}
+
+/// Every report in `text`: one document, or several one after another as
+/// printed for a list of users. Calendars are put back in chronological order,
+/// as for a fresh fetch, in case the file was edited or produced elsewhere.
+pub fn read_reports(text: &str) -> Result<Vec<SavedReport>, serde_json::Error> {
+    let mut reports: Vec<SavedReport> = serde_json::Deserializer::from_str(text).into_iter().collect::<Result<_, _>>()?;
+    for report in &mut reports {
+        if sort_chronologically(&mut report.calendar) {
+            log::debug!("calendar of {} in the saved report was not in chronological order, sorted it", report.login);
+        }
+    }
+    Ok(reports)
+}
+
+/// The numbers of the terminal's Statistics section
+#[derive(Serialize)]
+pub struct ReportStatistics {
+    pub total_contributions: u32,
+    pub active_days: usize,
+    pub max_per_day: u32,
+    pub average_per_active_day: f32,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub longest_gap: u32,
+    // Percentage of the days in the period with contributions
+    pub consistency_percent: f32,
+    pub daily_std_dev: f32,
+    // Earliest day with the most contributions
+    pub best_day: Option<String>,
+}
+
+pub fn report_statistics(calendar: &ContributionCalendar) -> ReportStatistics {
+    let (current_streak, longest_streak) = compute_streaks(calendar);
+    ReportStatistics {
+        total_contributions: calendar.total_contributions,
+        active_days: count_active_days(calendar),
+        max_per_day: max_contributions_per_day(calendar),
+        average_per_active_day: average_per_active_day(calendar),
+        current_streak,
+        longest_streak,
+        longest_gap: longest_gap(calendar),
+        consistency_percent: consistency_percent(calendar),
+        daily_std_dev: daily_std_dev(calendar),
+        best_day: best_day(calendar).map(|(day, _)| day.date.clone()),
+    }
+}
+
+/// One row per day, with `delimiter` between the fields: ',' for CSV, '\t' for TSV.
+/// No field can contain either, so nothing is quoted.
+pub fn print_delimited<W: Write>(calendar: &ContributionCalendar, delimiter: char, out: &mut W) -> io::Result<()> {
+    writeln!(out, "date{0}contribution_count{0}color", delimiter)?;
+    for day in calendar.weeks.iter().flat_map(|w| &w.contribution_days) {
+        writeln!(out, "{1}{0}{2}{0}{3}", delimiter, day.date, day.contribution_count, day.color)?;
+    }
+    Ok(())
+}
+
+// SVG layout, in pixels
+const SVG_CELL: usize = 10;
+
+const SVG_STEP: usize = 13;
+
+const SVG_LEFT: usize = 32;
+
+const SVG_TOP: usize = 20;
+
+pub fn render_svg<W: Write>(calendar: &ContributionCalendar, palette: &Palette, out: &mut W) -> io::Result<()> {
+    let width = SVG_LEFT + calendar.weeks.len() * SVG_STEP + 10;
+    let height = SVG_TOP + 7 * SVG_STEP + 10;
+    
+    writeln!(out, r##"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="sans-serif" font-size="9">"##, width, height)?;
+    writeln!(out, r##"<rect width="100%" height="100%" fill="#0d1117"/>"##)?;
+    
+    // Month labels above the first week that starts in a new month
+    let mut last_month = None;
+    for (week_idx, week) in calendar.weeks.iter().enumerate() {
+        let month = week_month(week);
+        if let Some(m) = month.filter(|_| month != last_month) {
+            writeln!(out, r##"<text x="{}" y="{}" fill="#74b9ff">{}</text>"##,
+                SVG_LEFT + week_idx * SVG_STEP, SVG_TOP - 6, MONTHS[m])?;
+            last_month = month;
+        }
+    }
+    
+    // Weekday labels
+    for (row, label) in [(1, "Mon"), (3, "Wed"), (5, "Fri")] {
+        writeln!(out, r##"<text x="0" y="{}" fill="#74b9ff">{}</text>"##,
+            SVG_TOP + row * SVG_STEP + SVG_CELL - 1, label)?;
+    }
+    
+    for (week_idx, week) in calendar.weeks.iter().enumerate() {
+        for (row, day) in week.contribution_days.iter().enumerate() {
+            let (r, g, b) = palette[intensity_level(day.contribution_count)];
+            writeln!(out, r##"<rect x="{}" y="{}" width="{}" height="{}" rx="2" fill="#{:02x}{:02x}{:02x}"><title>{}: {} contributions</title></rect>"##,
+                SVG_LEFT + week_idx * SVG_STEP, SVG_TOP + row * SVG_STEP, SVG_CELL, SVG_CELL,
+                r, g, b, day.date, day.contribution_count)?;
+        }
+    }
+    
+    writeln!(out, "</svg>")
+}
+
+// 3x5 pixel glyphs for the PNG labels, one bit per pixel (MSB on the left)
+fn png_glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b101, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b101, 0b111, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        _ => [0; 5],
+    }
+}
+
+// RGB pixel buffer used to rasterize the PNG
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+fn fill_rect(canvas: &mut Canvas, x: usize, y: usize, w: usize, h: usize, (r, g, b): (u8, u8, u8)) {
+    for py in y..std::cmp::min(y + h, canvas.height) {
+        for px in x..std::cmp::min(x + w, canvas.width) {
+            let i = (py * canvas.width + px) * 3;
+            canvas.pixels[i..i + 3].copy_from_slice(&[r, g, b]);
+        }
+    }
+}
+
+fn draw_text(canvas: &mut Canvas, x: usize, y: usize, scale: usize, text: &str, color: (u8, u8, u8)) {
+    for (i, c) in text.chars().enumerate() {
+        let glyph = png_glyph(c);
+        let glyph_x = x + i * 4 * scale;
+        for (row, bits) in glyph.iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) != 0 {
+                    fill_rect(canvas, glyph_x + col * scale, y + row * scale, scale, scale, color);
+                }
+            }
+        }
+    }
+}
+
+pub fn render_png<W: Write>(
+    calendar: &ContributionCalendar,
+    palette: &Palette,
+    cell_size: u32,
+    out: W,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let cell = cell_size as usize;
+    let gap = std::cmp::max(1, cell / 4);
+    let step = cell + gap;
+    // Labels are 5 pixels tall, scaled up with the cells
+    let scale = std::cmp::max(1, cell / 6);
+    let label_color = (116, 185, 255);
+    
+    let left = 3 * 4 * scale + 2 * gap;
+    let top = 5 * scale + 2 * gap;
+    let margin = step;
+    
+    let mut canvas = Canvas {
+        width: left + calendar.weeks.len() * step + margin,
+        height: top + 7 * step + margin,
+        pixels: Vec::new(),
+    };
+    canvas.pixels = [13, 17, 23].repeat(canvas.width * canvas.height);
+    
+    let mut last_month = None;
+    for (week_idx, week) in calendar.weeks.iter().enumerate() {
+        let month = week_month(week);
+        if let Some(m) = month.filter(|_| month != last_month) {
+            draw_text(&mut canvas, left + week_idx * step, gap, scale, MONTHS[m], label_color);
+            last_month = month;
+        }
+    }
+    
+    for (row, label) in [(1, "Mon"), (3, "Wed"), (5, "Fri")] {
+        let y = top + row * step + cell.saturating_sub(5 * scale) / 2;
+        draw_text(&mut canvas, gap, y, scale, label, label_color);
+    }
+    
+    for (week_idx, week) in calendar.weeks.iter().enumerate() {
+        for (row, day) in week.contribution_days.iter().enumerate() {
+            let color = palette[intensity_level(day.contribution_count)];
+            fill_rect(&mut canvas, left + week_idx * step, top + row * step, cell, cell, color);
+        }
+    }
+    
+    let mut encoder = png::Encoder::new(out, canvas.width as u32, canvas.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&canvas.pixels)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Writes an iCalendar feed with an all-day event for every day that has
+/// contributions. Event UIDs only depend on the user and the date, so
+/// calendar apps update the same events when the feed is imported again.
+pub fn render_ics<W: Write>(login: &str, calendar: &ContributionCalendar, out: &mut W) -> io::Result<()> {
+    // iCalendar lines end with CRLF
+    write!(out, "BEGIN:VCALENDAR\r\n")?;
+    write!(out, "VERSION:2.0\r\n")?;
+    write!(out, "PRODID:-//gitstat//Contributions//EN\r\n")?;
+    write!(out, "X-WR-CALNAME:GitHub contributions of {}\r\n", login)?;
+    
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    let active_days = calendar.weeks.iter()
+        .flat_map(|w| &w.contribution_days)
+        .filter(|day| day.contribution_count > 0);
+    for day in active_days {
+        let Ok(date) = chrono::NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+            continue;
+        };
+        let unit = if day.contribution_count == 1 { "contribution" } else { "contributions" };
+        write!(out, "BEGIN:VEVENT\r\n")?;
+        write!(out, "UID:{}-{}@gitstat\r\n", date.format("%Y%m%d"), login)?;
+        write!(out, "DTSTAMP:{}\r\n", stamp)?;
+        write!(out, "DTSTART;VALUE=DATE:{}\r\n", date.format("%Y%m%d"))?;
+        write!(out, "DTEND;VALUE=DATE:{}\r\n", (date + chrono::Days::new(1)).format("%Y%m%d"))?;
+        write!(out, "SUMMARY:{} {} on GitHub\r\n", day.contribution_count, unit)?;
+        // Shown as free time, the day isn't actually booked
+        write!(out, "TRANSP:TRANSPARENT\r\n")?;
+        write!(out, "END:VEVENT\r\n")?;
+    }
+    
+    write!(out, "END:VCALENDAR\r\n")
+}
+
+// Prometheus metrics: name, help text and the value for one profile
+type Metric = (&'static str, &'static str, fn(&GitHubUser, &ContributionsCollection) -> f64);
+
+const METRICS: [Metric; 12] = [
+    ("gitstat_total_contributions", "Contributions in the period", |_, c| c.contribution_calendar.total_contributions as f64),
+    ("gitstat_active_days", "Days with at least one contribution", |_, c| count_active_days(&c.contribution_calendar) as f64),
+    ("gitstat_max_per_day", "Most contributions on a single day", |_, c| max_contributions_per_day(&c.contribution_calendar) as f64),
+    ("gitstat_current_streak_days", "Consecutive active days up to today", |_, c| compute_streaks(&c.contribution_calendar).0 as f64),
+    ("gitstat_longest_streak_days", "Longest run of consecutive active days", |_, c| compute_streaks(&c.contribution_calendar).1 as f64),
+    ("gitstat_commits", "Commit contributions in the period", |_, c| c.breakdown.commits as f64),
+    ("gitstat_pull_requests", "Pull requests opened in the period", |_, c| c.breakdown.pull_requests as f64),
+    ("gitstat_issues", "Issues opened in the period", |_, c| c.breakdown.issues as f64),
+    ("gitstat_reviews", "Pull request reviews in the period", |_, c| c.breakdown.reviews as f64),
+    ("gitstat_public_repos", "Public repositories", |u, _| u.public_repos as f64),
+    ("gitstat_followers", "Followers", |u, _| u.followers as f64),
+    ("gitstat_following", "Accounts followed", |u, _| u.following as f64),
+];
+
+/// Writes every metric for all the profiles in the Prometheus text format.
+/// Samples are grouped by metric, as the format requires, so several users
+/// have to be printed in one call.
+pub fn print_prometheus<W: Write>(profiles: &[(&GitHubUser, &ContributionsCollection)], out: &mut W) -> io::Result<()> {
+    for (name, help, value) in METRICS {
+        writeln!(out, "# HELP {} {}", name, help)?;
+        writeln!(out, "# TYPE {} gauge", name)?;
+        for (user, contributions) in profiles {
+            writeln!(out, "{}{{user=\"{}\"}} {}", name, escape_label(&user.login), value(user, contributions))?;
+        }
+    }
+    Ok(())
+}
+
+// Label values are quoted, so backslashes, quotes and newlines are escaped
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+// Values selectable with --fields, named like the keys of the JSON report
+type Field = (&'static str, fn(&GitHubUser, &ContributionsCollection) -> serde_json::Value);
+
+pub const FIELDS: [Field; 19] = [
+    ("login", |u, _| u.login.as_str().into()),
+    ("name", |u, _| u.name.as_deref().into()),
+    ("public_repos", |u, _| u.public_repos.into()),
+    ("followers", |u, _| u.followers.into()),
+    ("following", |u, _| u.following.into()),
+    ("total_contributions", |_, c| c.contribution_calendar.total_contributions.into()),
+    ("active_days", |_, c| count_active_days(&c.contribution_calendar).into()),
+    ("max_per_day", |_, c| max_contributions_per_day(&c.contribution_calendar).into()),
+    ("average_per_active_day", |_, c| decimal(average_per_active_day(&c.contribution_calendar))),
+    ("current_streak", |_, c| compute_streaks(&c.contribution_calendar).0.into()),
+    ("longest_streak", |_, c| compute_streaks(&c.contribution_calendar).1.into()),
+    ("longest_gap", |_, c| longest_gap(&c.contribution_calendar).into()),
+    ("consistency_percent", |_, c| decimal(consistency_percent(&c.contribution_calendar))),
+    ("daily_std_dev", |_, c| decimal(daily_std_dev(&c.contribution_calendar))),
+    ("best_day", |_, c| best_day(&c.contribution_calendar).map(|(day, _)| day.date.clone()).into()),
+    ("commits", |_, c| c.breakdown.commits.into()),
+    ("pull_requests", |_, c| c.breakdown.pull_requests.into()),
+    ("issues", |_, c| c.breakdown.issues.into()),
+    ("reviews", |_, c| c.breakdown.reviews.into()),
+];
+
+// Widening the f32 directly would print 3.9 as 3.9000000953674316
+fn decimal(value: f32) -> serde_json::Value {
+    value.to_string().parse::<f64>().unwrap_or_default().into()
+}
+
+fn field_values(user: &GitHubUser, contributions: &ContributionsCollection, fields: &[String]) -> Vec<serde_json::Value> {
+    fields
+        .iter()
+        .filter_map(|name| FIELDS.iter().find(|(field, _)| field == name))
+        .map(|(_, value)| value(user, contributions))
+        .collect()
+}
+
+/// One JSON object with only the selected fields, in the order they were given
+pub fn print_fields_json<W: Write>(
+    user: &GitHubUser,
+    contributions: &ContributionsCollection,
+    fields: &[String],
+    pretty: bool,
+    out: &mut W,
+) -> io::Result<()> {
+    let values = field_values(user, contributions, fields);
+    let entries: Vec<String> = fields
+        .iter()
+        .zip(&values)
+        .map(|(name, value)| format!("{}:{}{}", serde_json::Value::from(name.as_str()), if pretty { " " } else { "" }, value))
+        .collect();
+    if pretty {
+        writeln!(out, "{{\n  {}\n}}", entries.join(",\n  "))
+    } else {
+        writeln!(out, "{{{}}}", entries.join(","))
+    }
+}
+
+/// A header with the selected fields, then one row per profile.
+/// CSV values holding a comma, quote or newline are quoted; tabs in TSV values become spaces.
+pub fn print_fields_delimited<W: Write>(
+    profiles: &[(&GitHubUser, &ContributionsCollection)],
+    fields: &[String],
+    delimiter: char,
+    out: &mut W,
+) -> io::Result<()> {
+    writeln!(out, "{}", fields.join(&delimiter.to_string()))?;
+    for (user, contributions) in profiles {
+        let row: Vec<String> = field_values(user, contributions, fields)
+            .into_iter()
+            .map(|value| {
+                let text = match value {
+                    serde_json::Value::Null => String::new(),
+                    serde_json::Value::String(text) => text,
+                    other => other.to_string(),
+                };
+                if delimiter == '\t' {
+                    text.replace(['\t', '\n'], " ")
+                } else if text.contains([',', '"', '\n']) {
+                    format!("\"{}\"", text.replace('"', "\"\""))
+                } else {
+                    text
+                }
+            })
+            .collect();
+        writeln!(out, "{}", row.join(&delimiter.to_string()))?;
+    }
+    Ok(())
+}
+
+pub fn print_summary<W: Write>(user: &GitHubUser, calendar: &ContributionCalendar, out: &mut W) -> io::Result<()> {
+    let (current_streak, _) = compute_streaks(calendar);
+    writeln!(out, "{}: {} contributions, {} active days, current streak {}",
+        user.login, calendar.total_contributions, count_active_days(calendar), current_streak)?;
+    Ok(())
+}
+
+pub fn print_markdown<W: Write>(user: &GitHubUser, calendar: &ContributionCalendar, options: &RenderOptions, out: &mut W) -> io::Result<()> {
+    let (current_streak, longest_streak) = compute_streaks(calendar);
+    let period = period_label(options);
+    
+    writeln!(out, "### GitHub Activity ({}) for {}", period, user.login)?;
+    writeln!(out)?;
+    writeln!(out, "| Statistic | Value |")?;
+    writeln!(out, "| --- | ---: |")?;
+    writeln!(out, "| Total Contributions | {} |", calendar.total_contributions)?;
+    writeln!(out, "| Active Days | {} |", count_active_days(calendar))?;
+    writeln!(out, "| Max/Day | {} |", max_contributions_per_day(calendar))?;
+    writeln!(out, "| Avg/Active Day | {:.1} |", average_per_active_day(calendar))?;
+    writeln!(out, "| Current Streak | {} |", current_streak)?;
+    writeln!(out, "| Longest Streak | {} |", longest_streak)?;
+    writeln!(out)?;
+    
+    // A code block keeps the grid aligned once GitHub renders the markdown
+    let weekdays = ["   ", "Mon", "   ", "Wed", "   ", "Fri", "   "];
+    writeln!(out, "```")?;
+    writeln!(out, "    {}", month_labels(&calendar.weeks, 1))?;
+    for (row, label) in weekdays.iter().enumerate() {
+        let cells: String = calendar.weeks.iter()
+            .map(|week| match week.contribution_days.get(row) {
+                Some(day) => LEVEL_ASCII_CHARS[cell_level(day.contribution_count, options)],
+                None => " ",
+            })
+            .collect();
+        writeln!(out, "{} {}", label, cells.trim_end())?;
+    }
+    if options.show_legend {
+        writeln!(out)?;
+        writeln!(out, "    Less {} More", LEVEL_ASCII_CHARS.concat())?;
+    }
+    writeln!(out, "```")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn saved_calendars_are_read_back_in_order() {
+        let day = |date: &str, count: u32| format!(r#"{{"date":"{}","contributionCount":{},"color":""}}"#, date, count);
+        let report = format!(
+            r#"{{"login":"alice","name":null,"public_repos":0,"followers":0,"following":0,
+                "breakdown":{{}},"calendar":{{"totalContributions":4,"weeks":[
+                {{"contributionDays":[{},{}]}},{{"contributionDays":[{},{}]}}]}}}}"#,
+            day("2024-01-09", 1), day("2024-01-08", 1), day("2024-01-02", 0), day("2024-01-01", 2)
+        );
+        let reports = read_reports(&report).unwrap();
+        let dates: Vec<&str> = reports[0].calendar.weeks.iter()
+            .flat_map(|w| &w.contribution_days)
+            .map(|d| d.date.as_str())
+            .collect();
+        assert_eq!(dates, ["2024-01-01", "2024-01-02", "2024-01-08", "2024-01-09"]);
+        // Out of order, the last two days would have made the current streak 1
+        assert_eq!(compute_streaks(&reports[0].calendar), (2, 2));
+    }
+}
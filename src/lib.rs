@@ -0,0 +1,27 @@
+//! Fetch and display GitHub contribution activity.
+//!
+//! The `gitstat` binary is a thin command line wrapper around this crate:
+//! [`api`] talks to GitHub, [`models`] holds the returned data, [`stats`]
+//! derives numbers from it and [`render`]/[`export`] turn it into output.
+//! [`cache`] and [`snapshot`] keep responses and history on disk, in the
+//! directories picked by [`paths`]. [`selftest`] checks a build offline.
+
+pub mod api;
+pub mod cache;
+pub mod config;
+pub mod error;
+pub mod export;
+pub mod models;
+pub mod paths;
+pub mod render;
+pub mod selftest;
+pub mod snapshot;
+pub mod stats;
+pub mod tui;
+
+pub use api::{get_user_contributions_real, get_user_info, Api};
+pub use error::GitStatError;
+pub use models::{
+    ContributionBreakdown, ContributionCalendar, ContributionDay, ContributionWeek,
+    ContributionsCollection, DateRange, GitHubUser, LanguageStat, RateLimit, RepoContributions,
+};
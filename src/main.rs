@@ -1,367 +1,1718 @@
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use futures::stream::{self, StreamExt};
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use colored::*;
+use serde::Serialize;
+use tokio::sync::Semaphore;
+use chrono::{Datelike, Months, NaiveDate};
 use std::env;
-use terminal_size::{Width, Height, terminal_size};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use unicode_width::UnicodeWidthChar;
+
+use gitstat::api::{describe_contributions_request, get_graphql_rate_limit, get_installation_token, get_org_members, get_rate_limit, get_repo_contributions, get_repository_activity, get_top_languages, get_total_stars, validate_token, Api, DEFAULT_API_URL, DEFAULT_CONCURRENCY, DEFAULT_TIMEOUT_SECS};
+use gitstat::cache::{load_user_contributions, load_user_info, CacheMode};
+use gitstat::config::load_config;
+use gitstat::error::GitStatError;
+use gitstat::export::{print_delimited, print_fields_delimited, print_fields_json, print_markdown, print_prometheus, print_summary, read_reports, render_ics, render_png, render_svg, report_statistics, OutputFormat, UserReport, FIELDS};
+use gitstat::paths;
+use gitstat::selftest;
+use gitstat::models::{ContributionCalendar, ContributionsCollection, DateRange, GitHubUser, LanguageStat, RepoContributions};
+use gitstat::snapshot::{record_snapshot, SnapshotDelta};
+use gitstat::stats::whole_year;
+use gitstat::tui;
+use gitstat::render::{
+    draw_braille_chart, draw_comparison, draw_contribution_breakdown, draw_period_comparison, draw_contribution_calendar, draw_goal, draw_header,
+    detect_color_mode, draw_languages, draw_leaderboard, draw_monthly_table, draw_repo_breakdown, draw_repo_header, draw_rolling, draw_sparkline, draw_yearly_totals, draw_compact_statistics, draw_statistics, draw_weekday_histogram, palette_for,
+    terminal_width, ColorMode, RenderOptions, Standing, Theme, MIN_WIDTH,
+};
 
 #[derive(Parser)]
 #[command(name = "gitstat")]
 #[command(about = "Display GitHub activity schema for any user")]
 struct Args {
-    /// GitHub username
-    username: String,
+    /// GitHub username (several can be given separated by commas, e.g. alice,bob)
+    #[arg(required_unless_present_any = ["completions", "rate_limit", "stdin", "selftest", "from_file", "repo"])]
+    username: Option<String>,
+
+    /// Second GitHub username to compare against, side by side
+    compare: Option<String>,
+
+    /// Treat the name as an organization (detected automatically otherwise)
+    #[arg(long)]
+    org: bool,
+
+    /// Rank the public members of the organization given by their contributions
+    #[arg(long, conflicts_with_all = ["compare", "stdin", "from_file", "all_time", "compare_period", "tui", "watch", "svg", "png", "ical"])]
+    org_members: bool,
+
+    /// Show the commit activity of a single repository, given as OWNER/NAME, instead of a profile
+    #[arg(long, value_name = "OWNER/NAME", value_parser = parse_repo, conflicts_with_all = ["username", "org", "org_members", "stdin", "from_file", "year", "since", "from", "to", "all_time", "compare_period", "tui", "check", "print_query"])]
+    repo: Option<(String, String)>,
     
-    /// GitHub access token (or use GITHUB_TOKEN environment variable)
+    /// GitHub access token (or use GITHUB_TOKEN / GH_TOKEN environment variable)
     #[arg(short, long)]
     token: Option<String>,
-}
 
-#[derive(Debug, Deserialize)]
-struct GitHubUser {
-    login: String,
-    name: Option<String>,
-    public_repos: u32,
-    followers: u32,
-    following: u32,
-}
+    /// Read the GitHub token from this file (or use GITHUB_TOKEN_FILE environment variable)
+    #[arg(long, value_name = "PATH")]
+    token_file: Option<PathBuf>,
 
-// Structures pour la requête GraphQL
-#[derive(Serialize)]
-struct GraphQLRequest {
-    query: String,
-    variables: GraphQLVariables,
-}
+    /// Authenticate as this GitHub App, through a token minted for one of its installations
+    #[arg(long, value_name = "ID", requires = "app_key", conflicts_with_all = ["token", "token_file"])]
+    app_id: Option<u64>,
 
-#[derive(Serialize)]
-struct GraphQLVariables {
-    username: String,
-}
+    /// Private key (PEM) of the GitHub App given with --app-id
+    #[arg(long, value_name = "PATH", requires = "app_id")]
+    app_key: Option<PathBuf>,
 
-// Structures pour la réponse GraphQL
-#[derive(Debug, Deserialize)]
-struct GraphQLResponse {
-    data: Option<GraphQLData>,
-    errors: Option<Vec<GraphQLError>>,
-}
+    /// Output format [default: terminal]
+    #[arg(short, long, value_enum)]
+    format: Option<OutputFormat>,
 
-#[derive(Debug, Deserialize)]
-struct GraphQLError {
-    message: String,
-}
+    /// Read usernames from stdin, one per line, and print one JSON object per
+    /// line; a profile that can't be fetched gives an object with an "error" field
+    #[arg(long, conflicts_with_all = ["username", "compare", "summary", "json_pretty"])]
+    stdin: bool,
 
-#[derive(Debug, Deserialize)]
-struct GraphQLData {
-    user: Option<GitHubUserWithContributions>,
-}
+    /// Render a saved `--format json` export instead of fetching anything
+    /// (several documents one after another are shown in turn)
+    #[arg(long, value_name = "PATH", conflicts_with_all = [
+        "username", "stdin", "compare", "all_time", "compare_period", "watch", "tui",
+        "svg", "png", "ical", "rate_limit", "print_query", "app_id",
+    ])]
+    from_file: Option<PathBuf>,
 
-#[derive(Debug, Deserialize)]
-struct GitHubUserWithContributions {
-    #[allow(dead_code)]
-    login: String,
-    #[allow(dead_code)]
-    name: Option<String>,
-    #[serde(rename = "contributionsCollection")]
-    contributions_collection: ContributionsCollection,
-}
+    /// Indent the JSON output (implies --format json)
+    #[arg(long, conflicts_with = "summary")]
+    json_pretty: bool,
+
+    /// Only output these comma-separated fields, e.g. login,total_contributions,current_streak
+    /// (JSON, CSV and TSV formats)
+    #[arg(long, value_name = "FIELDS", value_delimiter = ',', value_parser = parse_field)]
+    fields: Vec<String>,
+
+    /// Print a single summary line instead of the full output (same as --format summary)
+    #[arg(long, conflicts_with = "format")]
+    summary: bool,
+
+    /// Show contributions for a specific calendar year (e.g. 2022)
+    #[arg(short, long, conflicts_with_all = ["from", "to"])]
+    year: Option<i32>,
+
+    /// Show contributions for every year since the account was created, plus the grand total
+    #[arg(long, visible_alias = "since-creation", conflicts_with_all = ["year", "from", "to", "compare", "format", "summary", "watch"])]
+    all_time: bool,
+
+    /// Compare the period (the last year by default, or --year etc.) with the
+    /// same window one year earlier
+    #[arg(long, conflicts_with_all = ["all_time", "compare", "watch", "tui"])]
+    compare_period: bool,
+
+    /// Show the last N days, up to 365 (e.g. --since 90)
+    #[arg(long, value_name = "DAYS", conflicts_with_all = ["year", "from", "to", "all_time"], value_parser = clap::value_parser!(u32).range(1..))]
+    since: Option<u32>,
+
+    /// Start of a custom period, YYYY-MM-DD (periods can span at most one year)
+    #[arg(long, value_name = "DATE")]
+    from: Option<NaiveDate>,
+
+    /// End of a custom period, YYYY-MM-DD (inclusive)
+    #[arg(long, value_name = "DATE")]
+    to: Option<NaiveDate>,
+
+    /// Do not read or write the local response cache
+    #[arg(long, conflicts_with = "refresh")]
+    no_cache: bool,
+
+    /// Ignore cached responses and fetch fresh data (the cache is still updated)
+    #[arg(long)]
+    refresh: bool,
+
+    /// Keep cached responses in this directory instead of the platform's cache directory
+    #[arg(long, value_name = "PATH", conflicts_with = "no_cache")]
+    cache_dir: Option<PathBuf>,
+
+    /// Heatmap color theme [default: blue]
+    #[arg(long, value_enum)]
+    theme: Option<Theme>,
+
+    /// How many colors to use; auto reads COLORTERM and TERM
+    #[arg(long, value_enum, default_value_t = ColorMode::Auto)]
+    color_mode: ColorMode,
+
+    /// Color each day with the exact shade GitHub reports for it
+    #[arg(long, conflicts_with = "emoji")]
+    native_colors: bool,
+
+    /// Draw each day as a blank cell on a gray background that brightens with
+    /// its count, for a smooth heatmap instead of five levels
+    #[arg(long, conflicts_with_all = ["emoji", "native_colors"])]
+    grayscale_bg: bool,
+
+    /// Draw the calendar with plain ASCII characters instead of Unicode squares
+    #[arg(long)]
+    ascii: bool,
+
+    /// Character for days without contributions, e.g. '·' or ' '
+    #[arg(long, value_name = "CHAR", value_parser = parse_empty_char, conflicts_with = "emoji")]
+    empty_char: Option<char>,
+
+    /// Draw the calendar with colored square emoji, handy for chat pastes
+    /// (needs a terminal font with emoji)
+    #[arg(long, conflicts_with = "ascii")]
+    emoji: bool,
+
+    /// Draw the calendar levels with shading glyphs (░▒▓█) as well as colors,
+    /// e.g. along with the deuteranopia and protanopia themes
+    #[arg(long, conflicts_with_all = ["emoji", "ascii", "numbers", "grayscale_bg"])]
+    shaded: bool,
+
+    /// Only highlight days with at least N contributions in the calendar;
+    /// quieter days are drawn as empty (statistics still count every day)
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    min_contributions: u32,
+
+    /// Collapse each week into a single bar instead of seven daily rows
+    #[arg(long)]
+    by_week: bool,
+
+    /// Draw a compact calendar with two weeks per column, for narrow terminals
+    #[arg(long, conflicts_with = "by_week")]
+    mini: bool,
+
+    /// Print each day's contribution count in the calendar instead of a square
+    /// (100 or more show as ++)
+    #[arg(long, conflicts_with_all = ["emoji", "mini", "by_week", "grayscale_bg"])]
+    numbers: bool,
+
+    /// Continue the calendar on further rows when it doesn't fit the width,
+    /// instead of leaving out the most recent weeks
+    #[arg(long, conflicts_with = "mini")]
+    wrap: bool,
+
+    /// Reveal the calendar one week at a time, e.g. for screen recordings
+    /// (only when printing to a terminal)
+    #[arg(long, conflicts_with_all = ["mini", "by_week"])]
+    animate: bool,
+
+    /// Milliseconds to wait between weeks with --animate
+    #[arg(long, value_name = "MS", default_value_t = 15)]
+    animate_speed: u64,
+
+    /// Also print a table of contributions per month
+    #[arg(long)]
+    monthly: bool,
+
+    /// Also print a one-line sparkline of weekly totals
+    #[arg(long)]
+    sparkline: bool,
+
+    /// Also draw a line chart of daily contributions in braille characters
+    #[arg(long)]
+    chart: bool,
+
+    /// Also chart the rolling sum of contributions over the last N days [default: 30]
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "30", value_parser = clap::value_parser!(u16).range(1..))]
+    rolling: Option<u16>,
+
+    /// Show progress towards a goal of N contributions for the year
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    goal: Option<u32>,
+
+    /// Also show the most used languages across the user's repositories
+    #[arg(long)]
+    languages: bool,
+
+    /// Also show the repositories that received the most commits
+    #[arg(long)]
+    by_repo: bool,
+
+    /// Also show the total stars received across the user's public repositories
+    #[arg(long)]
+    stars: bool,
+
+    /// Record today's repo, follower and contribution counts in a local history
+    /// and show the change since the previous snapshot
+    #[arg(long)]
+    snapshot: bool,
+
+    /// Lay the output out for N columns instead of the detected terminal width
+    #[arg(long, value_name = "N")]
+    width: Option<usize>,
+
+    /// Write the output to this file instead of stdout, without colors
+    #[arg(short, long, value_name = "PATH", conflicts_with = "watch")]
+    output: Option<PathBuf>,
 
-#[derive(Debug, Deserialize)]
-struct ContributionsCollection {
-    #[serde(rename = "contributionCalendar")]
-    contribution_calendar: ContributionCalendar,
+    /// Write the contribution calendar as an SVG image to this path
+    /// (nothing is printed unless --format is also given)
+    #[arg(long, value_name = "PATH", conflicts_with = "compare")]
+    svg: Option<PathBuf>,
+
+    /// Write the contribution calendar as a PNG image to this path
+    /// (nothing is printed unless --format is also given)
+    #[arg(long, value_name = "PATH", conflicts_with = "compare")]
+    png: Option<PathBuf>,
+
+    /// Write the days with contributions as iCalendar events to this path
+    /// (nothing is printed unless --format is also given)
+    #[arg(long, value_name = "PATH", conflicts_with = "compare")]
+    ical: Option<PathBuf>,
+
+    /// Size in pixels of one day square in the PNG image
+    #[arg(long, value_name = "PIXELS", default_value_t = 12, value_parser = clap::value_parser!(u32).range(2..=64))]
+    cell_size: u32,
+
+    /// Number of times to retry a request after a network error or 5xx response
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    retries: u32,
+
+    /// Never send more than N requests to GitHub at the same time
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_CONCURRENCY, value_parser = clap::value_parser!(u16).range(1..))]
+    concurrency: u16,
+
+    /// Wait this many milliseconds after each request before sending the next
+    #[arg(long, value_name = "MS", default_value_t = 0)]
+    delay_ms: u64,
+
+    /// GitHub API base URL, e.g. https://github.example.com/api/v3 for GitHub Enterprise
+    /// (or use GITHUB_API_URL environment variable)
+    #[arg(long, value_name = "URL")]
+    base_url: Option<String>,
+
+    /// Send all requests through this HTTP(S) or SOCKS5 proxy, e.g. socks5://localhost:1080
+    /// (or use HTTPS_PROXY environment variable)
+    #[arg(long, value_name = "URL")]
+    proxy: Option<String>,
+
+    /// Don't check the token with an extra request before fetching data
+    #[arg(long)]
+    skip_validation: bool,
+
+    /// Only check the setup: validate the token, look up the user and show
+    /// the remaining API quota, without fetching any contributions
+    #[arg(long, conflicts_with = "skip_validation")]
+    check: bool,
+
+    /// Show the token's remaining GraphQL quota and when it resets, then exit
+    #[arg(long, conflicts_with = "check")]
+    rate_limit: bool,
+
+    /// Give up on a request after this many seconds [default: 30]
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+
+    /// Read defaults from this TOML file instead of config.toml in the config
+    /// directory (~/.config/gitstat on Linux)
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Keep running and refresh the output every SECONDS (stop with Ctrl-C)
+    #[arg(long, value_name = "SECONDS", conflicts_with = "compare", value_parser = clap::value_parser!(u64).range(1..))]
+    watch: Option<u64>,
+
+    /// Explore the calendar in a full-screen interactive view
+    #[arg(long, conflicts_with_all = ["compare", "watch", "all_time", "format", "summary", "output"])]
+    tui: bool,
+
+    /// Log requests, response statuses, timings, body sizes and rate-limit headers to stderr
+    /// (RUST_LOG=gitstat=trace also logs request headers, with the token redacted)
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Print the GraphQL query and variables sent for the contribution calendar, then
+    /// exit without sending anything
+    #[arg(long, conflicts_with = "org")]
+    print_query: bool,
+
+    /// Run offline checks of this build (config, palettes, dates, rendering) and exit,
+    /// with a non-zero status if any fails
+    #[arg(long)]
+    selftest: bool,
+
+    /// Print a shell completion script and exit
+    #[arg(long, value_name = "SHELL", hide = true)]
+    completions: Option<clap_complete::Shell>,
+
+    /// Leave out the profile header of the terminal output
+    #[arg(long)]
+    no_header: bool,
+
+    /// Leave out the contribution calendar of the terminal output
+    #[arg(long)]
+    no_calendar: bool,
+
+    /// Leave out the Statistics section of the terminal output
+    #[arg(long)]
+    no_stats: bool,
+
+    /// Leave out the "Less ... More" legend under the calendar, e.g. for tight screenshots
+    #[arg(long)]
+    legend_off: bool,
+
+    /// Condense the Statistics section into a single line, e.g. when listing several users
+    #[arg(long, conflicts_with = "no_stats")]
+    compact_stats: bool,
+
+    /// Disable colored output (also honored via the NO_COLOR environment variable)
+    #[arg(long)]
+    no_color: bool,
 }
 
-#[derive(Debug, Deserialize)]
-struct ContributionCalendar {
-    #[serde(rename = "totalContributions")]
-    total_contributions: u32,
-    weeks: Vec<ContributionWeek>,
+// Extra sections shown with a single profile, each only filled in on request
+#[derive(Default)]
+struct ProfileSections {
+    languages: Option<Vec<LanguageStat>>,
+    repos: Option<Vec<RepoContributions>>,
+    stars: Option<u64>,
+    delta: Option<SnapshotDelta>,
 }
 
-#[derive(Debug, Deserialize)]
-struct ContributionWeek {
-    #[serde(rename = "contributionDays")]
-    contribution_days: Vec<ContributionDay>,
+// Which step of fetching a profile failed
+enum FetchError {
+    User(GitStatError),
+    Contributions(GitStatError),
+    Unsupported(&'static str),
 }
 
-#[derive(Debug, Deserialize, Clone)]
-struct ContributionDay {
-    #[allow(dead_code)]
-    date: String,
-    #[serde(rename = "contributionCount")]
-    contribution_count: u32,
-    #[allow(dead_code)]
-    color: String,
+// The --stdin line of a profile that couldn't be fetched
+#[derive(Serialize)]
+struct BatchError<'a> {
+    login: &'a str,
+    error: String,
 }
 
+// Profiles fetched at the same time when several usernames are given
+const MAX_CONCURRENT_FETCHES: usize = 4;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    
+    // Purely local, so handled before reading the config or needing a token
+    if let Some(shell) = args.completions {
+        let mut script = Vec::new();
+        clap_complete::generate(shell, &mut Args::command(), "gitstat", &mut script);
+        // Piping into e.g. `head` closes stdout early, which is not an error
+        return match io::stdout().lock().write_all(&script) {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e.into()),
+            _ => Ok(()),
+        };
+    }
+    if args.selftest {
+        let passed = selftest::run(&mut io::stdout())?;
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+    let names = if args.stdin {
+        match io::read_to_string(io::stdin()) {
+            Ok(names) => names,
+            Err(e) => {
+                eprintln!("Error: could not read usernames from stdin: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        args.username.take().unwrap_or_default()
+    };
+    let separator = if args.stdin { '\n' } else { ',' };
+    let usernames: Vec<String> = names.split(separator)
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    // --rate-limit is about the token, so it doesn't need a username; a GitHub App
+    // installation is looked up by the repository's owner with --repo
+    let username = usernames.first().cloned()
+        .or_else(|| args.repo.as_ref().map(|(owner, _)| owner.clone()))
+        .unwrap_or_default();
+    if username.is_empty() && !args.rate_limit && args.from_file.is_none() {
+        eprintln!("Error: no username given");
+        std::process::exit(1);
+    }
+    if usernames.len() > 1 || args.stdin {
+        if args.compare.is_some() {
+            eprintln!("Error: either list several usernames or compare two users, not both");
+            std::process::exit(1);
+        }
+        let single_only = [
+            ("--all-time", args.all_time),
+            ("--compare-period", args.compare_period),
+            ("--tui", args.tui),
+            ("--watch", args.watch.is_some()),
+            ("--svg", args.svg.is_some()),
+            ("--png", args.png.is_some()),
+            ("--ical", args.ical.is_some()),
+            ("--org-members", args.org_members),
+        ];
+        if let Some((flag, _)) = single_only.iter().find(|(_, used)| *used) {
+            eprintln!("Error: {} only works with a single username", flag);
+            std::process::exit(1);
+        }
+    }
     
-    // Get token from arguments or environment variables
-    let token = args.token.or_else(|| env::var("GITHUB_TOKEN").ok());
+    // RUST_LOG takes precedence so verbosity can be tuned per module
+    let default_filter = if args.verbose { "gitstat=debug" } else { "warn" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_filter))
+        .format_timestamp(None)
+        .init();
+    // Only an explicit --format on the command line makes --svg/--png/--ical print too
+    let format_requested = args.format.is_some() || args.summary || args.json_pretty;
     
-    if token.is_none() {
-        eprintln!("Error: GitHub token required!");
-        eprintln!("You can:");
-        eprintln!("   1. Pass token with --token YOUR_TOKEN");
-        eprintln!("   2. Set GITHUB_TOKEN environment variable");
-        eprintln!("   3. Create a token at: https://github.com/settings/tokens");
-        eprintln!("      (Required permissions: 'read:user' only)");
+    let config = match load_config(args.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    args.theme = args.theme.or(config.theme);
+    args.format = args.format.or(config.format);
+    args.timeout = args.timeout.or(config.timeout);
+    
+    if args.no_color || env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        colored::control::set_override(false);
+    }
+    
+    // Opened before any request so a bad path fails fast
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => match fs::File::create(path) {
+            Ok(file) => {
+                colored::control::set_override(false);
+                Box::new(io::BufWriter::new(file))
+            }
+            Err(e) => {
+                eprintln!("Error: could not create {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        },
+        None => Box::new(io::stdout()),
+    };
+    
+    if args.tui && !io::stdout().is_terminal() {
+        eprintln!("Error: --tui needs an interactive terminal");
         std::process::exit(1);
     }
     
-    let token = token.unwrap();
+    if let Some(year) = args.year {
+        let current_year = chrono::Utc::now().year();
+        if year > current_year {
+            eprintln!("Error: year {} is in the future (current year is {})", year, current_year);
+            std::process::exit(1);
+        }
+    }
+    // GitHub serves at most one year per contributions query
+    let last_days = args.since.map(|days| {
+        if days > MAX_SINCE_DAYS {
+            eprintln!("Warning: --since {} is longer than GitHub allows, using {}", days, MAX_SINCE_DAYS);
+        }
+        days.min(MAX_SINCE_DAYS)
+    });
+    let period = match last_days {
+        Some(days) => Some(last_days_period(days)),
+        None => match resolve_period(args.year, args.from, args.to) {
+            Ok(period) => period,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+    };
+    
+    // A GitHub App's token is minted once the client is built, but a bad key fails here
+    let app = match (args.app_id, args.app_key.take()) {
+        (Some(app_id), Some(path)) => match load_app_key(&path) {
+            Ok(key) => Some((app_id, key)),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        _ => None,
+    };
+    
+    // Get token from arguments or environment variables, then the config file,
+    // then the GitHub CLI
+    let token = if app.is_some() {
+        None
+    } else {
+        match resolve_token(args.token.take(), args.token_file.take(), config.token, |name| env::var_os(name), gh_cli_token) {
+            Ok(token) => token,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    };
     
-    let client = Client::new();
+    // Without a token only the public profile header can be shown, since the
+    // calendar comes from GraphQL which always requires authentication
+    let anonymous = token.is_none() && app.is_none();
+    let header_only = matches!(args.format, None | Some(OutputFormat::Terminal))
+        && !args.summary
+        && !args.json_pretty
+        && !args.check
+        && !args.rate_limit
+        && !args.all_time
+        && !args.compare_period
+        && !args.tui
+        && args.compare.is_none()
+        && args.watch.is_none()
+        && args.svg.is_none()
+        && args.png.is_none()
+        && args.ical.is_none()
+        && !args.org_members
+        && args.repo.is_none();
+    if anonymous && !header_only && !args.print_query && args.from_file.is_none() {
+        eprintln!("Error: GitHub token required!");
+        print_token_help();
+        std::process::exit(1);
+    }
     
-    // Get basic user information
-    match get_user_info(&client, &args.username).await {
-        Ok(user) => {
-            // Get and display real contributions
-            match get_user_contributions_real(&client, &args.username, &token).await {
-                Ok(contributions) => {
-                    display_user_profile(&user, &contributions);
-                }
-                Err(e) => {
-                    eprintln!("Error retrieving contributions: {}", e);
-                    eprintln!("Please verify your token is valid and has proper permissions");
+    let timeout = args.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS);
+    let mut client = Client::builder().timeout(std::time::Duration::from_secs(timeout));
+    let proxy = args.proxy.take()
+        .or_else(|| env::var("HTTPS_PROXY").ok())
+        .or_else(|| env::var("https_proxy").ok())
+        .filter(|url| !url.trim().is_empty());
+    if let Some(url) = proxy {
+        // reqwest accepts any scheme here and only fails once a request is sent
+        if let Some((scheme, _)) = url.trim().split_once("://") {
+            if !["http", "https", "socks5", "socks5h"].contains(&scheme.to_lowercase().as_str()) {
+                eprintln!("Error: invalid proxy URL '{}': unsupported scheme '{}'", url, scheme);
+                std::process::exit(1);
+            }
+        }
+        match reqwest::Proxy::all(url.trim()) {
+            Ok(proxy) => client = client.proxy(proxy),
+            Err(e) => {
+                // reqwest only says "builder error"; the innermost cause is more useful
+                let mut reason: &dyn std::error::Error = &e;
+                while let Some(cause) = reason.source() {
+                    reason = cause;
                 }
+                eprintln!("Error: invalid proxy URL '{}': {}", url, reason);
+                std::process::exit(1);
             }
         }
-        Err(e) => {
+    }
+    let api = Api {
+        client: client.build()?,
+        base_url: args.base_url.take()
+            .or_else(|| env::var("GITHUB_API_URL").ok())
+            .filter(|url| !url.trim().is_empty())
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+        retries: args.retries,
+        timeout_secs: timeout,
+        limiter: Semaphore::new(usize::from(args.concurrency)),
+        delay_ms: args.delay_ms,
+    };
+    
+    if args.print_query {
+        for (idx, name) in usernames.iter().enumerate() {
+            if idx > 0 {
+                println!();
+            }
+            println!("{}", describe_contributions_request(&api.base_url, name, period));
+        }
+        return Ok(());
+    }
+    
+    let token = match &app {
+        Some((app_id, key)) => match get_installation_token(&api, *app_id, key, &username).await {
+            Ok(token) => token,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => token.unwrap_or_default(),
+    };
+    
+    // Installation tokens can't read /user, and minting one already proved the key
+    if !args.skip_validation && !anonymous && app.is_none() && args.from_file.is_none() {
+        if let Err(e) = validate_token(&api, &token).await {
             eprintln!("Error: {}", e);
+            print_token_help();
             std::process::exit(1);
         }
     }
     
-    Ok(())
-}
-
-async fn get_user_info(client: &Client, username: &str) -> Result<GitHubUser, Box<dyn std::error::Error>> {
-    let url = format!("https://api.github.com/users/{}", username);
-    let response = client
-        .get(&url)
-        .header("User-Agent", "gitstat-cli")
-        .send()
-        .await?;
-    
-    if response.status().is_success() {
-        let user: GitHubUser = response.json().await?;
-        Ok(user)
-    } else {
-        Err(format!("User '{}' not found", username).into())
+    if args.rate_limit {
+        match get_graphql_rate_limit(&api, &token).await {
+            Ok(Some(limit)) => println!(
+                "GraphQL rate limit: {}/{} points remaining, resets {}",
+                limit.remaining,
+                limit.limit,
+                reset_in_words(limit.reset)
+            ),
+            Ok(None) => println!("GraphQL rate limit: not enforced by this server"),
+            Err(e) => {
+                eprintln!("Error: could not read the rate limit: {}", e);
+                print_error_hint(&e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
     }
-}
-
-async fn get_user_contributions_real(
-    client: &Client,
-    username: &str,
-    token: &str,
-) -> Result<ContributionCalendar, Box<dyn std::error::Error>> {
-    let query = r#"
-        query($username: String!) {
-            user(login: $username) {
-                login
-                name
-                contributionsCollection {
-                    contributionCalendar {
-                        totalContributions
-                        weeks {
-                            contributionDays {
-                                date
-                                contributionCount
-                                color
-                            }
-                        }
-                    }
-                }
+    
+    if args.check {
+        // Bypass the cache so the lookup really reaches GitHub
+        if let Err(e) = load_user_info(&api, &username, args.org, &token, CacheMode::Off).await {
+            report_fetch_error(&username, FetchError::User(e));
+            std::process::exit(1);
+        }
+        match get_rate_limit(&api, &token).await {
+            Ok(Some(limit)) => {
+                println!("OK");
+                println!("Rate limit: {}/{} requests remaining, resets {}", limit.remaining, limit.limit, reset_in_words(limit.reset));
+            }
+            Ok(None) => {
+                println!("OK");
+                println!("Rate limit: not enforced by this server");
+            }
+            Err(e) => {
+                eprintln!("Error: could not read the rate limit: {}", e);
+                std::process::exit(1);
             }
         }
-    "#;
+        return Ok(());
+    }
+    
+    if let Some(dir) = args.cache_dir.take() {
+        paths::set_cache_dir(dir);
+    }
+    let cache_mode = if args.no_cache {
+        CacheMode::Off
+    } else if args.refresh {
+        CacheMode::Refresh
+    } else {
+        CacheMode::Use
+    };
+    
+    let format = if args.stdin {
+        if args.format.is_some_and(|format| format != OutputFormat::Json) {
+            eprintln!("Error: --stdin always prints JSON Lines");
+            std::process::exit(1);
+        }
+        OutputFormat::Json
+    } else if args.summary {
+        OutputFormat::Summary
+    } else if args.json_pretty {
+        if args.format.is_some_and(|format| format != OutputFormat::Json) {
+            eprintln!("Error: --json-pretty only applies to the JSON format");
+            std::process::exit(1);
+        }
+        OutputFormat::Json
+    } else {
+        args.format.unwrap_or(OutputFormat::Terminal)
+    };
+    if !args.fields.is_empty() && !matches!(format, OutputFormat::Json | OutputFormat::Csv | OutputFormat::Tsv) {
+        eprintln!("Error: --fields only applies to the JSON, CSV and TSV formats");
+        std::process::exit(1);
+    }
+    // Exporting to a file is silent unless a format was explicitly requested
+    let print_output = (args.svg.is_none() && args.png.is_none() && args.ical.is_none()) || format_requested;
     
-    let request = GraphQLRequest {
-        query: query.to_string(),
-        variables: GraphQLVariables {
-            username: username.to_string(),
+    if args.compare.is_some() && format != OutputFormat::Terminal {
+        eprintln!("Error: comparing two users is only supported with the terminal format");
+        std::process::exit(1);
+    }
+    
+    let hide_all = args.no_header && args.no_calendar && args.no_stats;
+    if hide_all {
+        eprintln!("Warning: --no-header, --no-calendar and --no-stats together leave out every main section, showing them anyway");
+    }
+    
+    let render_options = RenderOptions {
+        period,
+        last_days,
+        palette: palette_for(args.theme.unwrap_or(Theme::Blue)),
+        color_mode: match args.color_mode {
+            ColorMode::Auto => detect_color_mode(),
+            mode => mode,
+        },
+        ascii: args.ascii,
+        emoji: args.emoji,
+        shaded: args.shaded,
+        empty_char: args.empty_char,
+        min_contributions: args.min_contributions,
+        by_week: args.by_week,
+        mini: args.mini,
+        numbers: args.numbers,
+        wrap: args.wrap,
+        animate: (args.animate && args.output.is_none() && io::stdout().is_terminal())
+            .then(|| std::time::Duration::from_millis(args.animate_speed)),
+        monthly: args.monthly,
+        goal: args.goal,
+        sparkline: args.sparkline,
+        chart: args.chart,
+        rolling: args.rolling.map(usize::from),
+        native_colors: args.native_colors,
+        grayscale_bg: args.grayscale_bg,
+        json_pretty: args.json_pretty,
+        fields: args.fields.clone(),
+        show_header: hide_all || !args.no_header,
+        show_calendar: hide_all || !args.no_calendar,
+        show_stats: hide_all || !args.no_stats,
+        show_legend: !args.legend_off,
+        compact_stats: args.compact_stats,
+        width: match args.width {
+            Some(width) if width < MIN_WIDTH => {
+                eprintln!("Warning: --width {} is too narrow, using {}", width, MIN_WIDTH);
+                MIN_WIDTH
+            }
+            Some(width) => width,
+            None => terminal_width(),
         },
     };
     
-    let response = client
-        .post("https://api.github.com/graphql")
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "gitstat-cli")
-        .json(&request)
-        .send()
-        .await?;
+    if let Some(path) = &args.from_file {
+        let reports = fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))
+            .and_then(|text| {
+                read_reports(&text).map_err(|e| format!("{} is not a gitstat JSON export: {}", path.display(), e))
+            });
+        let reports = match reports {
+            Ok(reports) if reports.is_empty() => {
+                eprintln!("Error: {} is empty", path.display());
+                std::process::exit(1);
+            }
+            Ok(reports) => reports,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        for (idx, report) in reports.into_iter().enumerate() {
+            if idx > 0 && matches!(format, OutputFormat::Terminal | OutputFormat::Markdown) {
+                writeln!(out)?;
+            }
+            let user = GitHubUser {
+                login: report.login,
+                name: report.name,
+                public_repos: report.public_repos,
+                followers: report.followers,
+                following: report.following,
+                account_type: None,
+                created_at: None,
+            };
+            let contributions = ContributionsCollection {
+                breakdown: report.breakdown,
+                contribution_calendar: report.calendar,
+                restricted_contributions: 0,
+            };
+            let sections = ProfileSections {
+                languages: report.languages,
+                repos: report.repositories,
+                stars: report.stars,
+                delta: None,
+            };
+            display_user_profile(&user, &contributions, &sections, format, &render_options, &mut out)?;
+        }
+        out.flush()?;
+        return Ok(());
+    }
     
-    if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()).into());
+    if let Some(interval) = args.watch {
+        if interval < 60 {
+            eprintln!("Warning: refreshing every {} seconds bypasses the cache and may hit the GitHub API rate limit", interval);
+        }
+        // Leave the prompt on a fresh line instead of in the middle of the output
+        tokio::spawn(async {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                println!();
+                std::process::exit(0);
+            }
+        });
     }
     
-    let graphql_response: GraphQLResponse = response.json().await?;
+    if anonymous {
+        eprintln!("Note: no GitHub token found, so only public profile data is shown (the contribution calendar needs a token)");
+        let mut failed = false;
+        for name in &usernames {
+            // The users endpoint also answers for organizations
+            match load_user_info(&api, name, false, &token, cache_mode).await {
+                Ok(user) => draw_header(&user, None, None, render_options.width, &mut out)?,
+                Err(e) => {
+                    report_fetch_error(name, FetchError::User(e));
+                    failed = true;
+                }
+            }
+        }
+        out.flush()?;
+        if failed {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
     
-    if let Some(errors) = graphql_response.errors {
-        let error_messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
-        return Err(format!("GraphQL errors: {}", error_messages.join(", ")).into());
+    if args.org_members {
+        if format != OutputFormat::Terminal {
+            eprintln!("Error: --org-members is only supported with the terminal format");
+            std::process::exit(1);
+        }
+        let members = match get_org_members(&api, &username, &token).await {
+            Ok(members) => members,
+            Err(e) => {
+                report_fetch_error(&username, FetchError::User(e));
+                std::process::exit(1);
+            }
+        };
+        // The API limiter keeps these concurrent fetches within --concurrency
+        let (api, token) = (&api, token.as_str());
+        let results: Vec<_> = stream::iter(members)
+            .map(|login| async move {
+                let result = load_user_contributions(api, &login, token, period, false, cache_mode).await;
+                (login, result)
+            })
+            .buffer_unordered(MAX_CONCURRENT_FETCHES)
+            .collect()
+            .await;
+        let mut board = Vec::new();
+        for (login, result) in results {
+            match result {
+                Ok(contributions) => board.push((login, Standing::Ranked(contributions.contribution_calendar.total_contributions))),
+                // Every remaining member would fail the same way
+                Err(e @ GitStatError::RateLimited { .. }) => {
+                    report_fetch_error(&login, FetchError::Contributions(e));
+                    std::process::exit(1);
+                }
+                // What GitHub answers for contributions the token can't see
+                Err(e @ (GitStatError::NotFound { .. } | GitStatError::GraphQl(_))) => {
+                    log::debug!("contributions of {} are not visible: {}", login, e);
+                    board.push((login, Standing::Private));
+                }
+                Err(e) => {
+                    eprintln!("Warning: could not retrieve contributions for '{}': {}", login, e);
+                    board.push((login, Standing::Failed));
+                }
+            }
+        }
+        // Highest total first, then private members, then failures, ties by name
+        board.sort_by_key(|(login, standing)| {
+            let order = match standing {
+                Standing::Ranked(total) => (0, std::cmp::Reverse(*total)),
+                Standing::Private => (1, std::cmp::Reverse(0)),
+                Standing::Failed => (2, std::cmp::Reverse(0)),
+            };
+            (order, login.to_lowercase())
+        });
+        draw_leaderboard(&username, &board, render_options.width, &render_options, &mut out)?;
+        out.flush()?;
+        return Ok(());
     }
     
-    let data = graphql_response
-        .data
-        .ok_or("No data returned by API")?;
-        
-    let user = data
-        .user
-        .ok_or(format!("User '{}' not found", username))?;
+    if let Some((owner, name)) = &args.repo {
+        if !matches!(format, OutputFormat::Terminal | OutputFormat::Csv | OutputFormat::Tsv) {
+            eprintln!("Error: --repo is only supported with the terminal, CSV and TSV formats");
+            std::process::exit(1);
+        }
+        let contributions = match get_repository_activity(&api, owner, name, &token).await {
+            Ok(contributions) => contributions,
+            Err(e) => {
+                eprintln!("Error retrieving the commit activity of '{}/{}': {}", owner, name, e);
+                print_error_hint(&e);
+                std::process::exit(1);
+            }
+        };
+        let calendar = &contributions.contribution_calendar;
+        match format {
+            OutputFormat::Csv => print_delimited(calendar, ',', &mut out)?,
+            OutputFormat::Tsv => print_delimited(calendar, '\t', &mut out)?,
+            _ => {
+                let term_width = render_options.width;
+                let calendar_width = calendar_width(calendar, term_width);
+                if render_options.show_header {
+                    draw_repo_header(owner, name, term_width, &mut out)?;
+                }
+                if render_options.show_calendar {
+                    draw_contribution_calendar(calendar, calendar_width, term_width, &render_options, &mut out)?;
+                }
+                if render_options.show_stats && render_options.compact_stats {
+                    draw_compact_statistics(calendar, term_width, &mut out)?;
+                } else if render_options.show_stats {
+                    draw_statistics(calendar, 0, term_width, &mut out)?;
+                }
+            }
+        }
+        out.flush()?;
+        return Ok(());
+    }
     
-    Ok(user.contributions_collection.contribution_calendar)
-}
-
-fn display_user_profile(user: &GitHubUser, calendar: &ContributionCalendar) {
-    let (term_width, _) = if let Some((Width(w), Height(h))) = terminal_size() {
-        (w as usize, h as usize)
-    } else {
-        (80, 24) 
-    };
+    if usernames.len() > 1 || args.stdin {
+        // Profiles are printed in the order given while the next ones are fetched
+        let mut profiles = stream::iter(&usernames)
+            .map(|name| fetch_profile(&api, name, &token, period, args.org, cache_mode))
+            .buffered(MAX_CONCURRENT_FETCHES);
+        let mut failed = Vec::new();
+        let mut shown = 0;
+        // Prometheus samples are grouped per metric and field rows share one header,
+        // so those are printed together at the end
+        let batched = format == OutputFormat::Prometheus
+            || (!args.fields.is_empty() && matches!(format, OutputFormat::Csv | OutputFormat::Tsv));
+        let mut batch = Vec::new();
+        for name in &usernames {
+            let Some(result) = profiles.next().await else { break };
+            match result {
+                Ok(profile) if batched => batch.push(profile),
+                Ok((user, contributions)) => {
+                    // Keep the terminal and Markdown layouts readable; other formats stay one record after another
+                    if shown > 0 && matches!(format, OutputFormat::Terminal | OutputFormat::Markdown) {
+                        writeln!(out)?;
+                    }
+                    let sections = fetch_sections(&api, &args, &token, period, &user, &contributions).await;
+                    display_user_profile(&user, &contributions, &sections, format, &render_options, &mut out)?;
+                    shown += 1;
+                }
+                // Keep the output one JSON object per username
+                Err(e) if args.stdin => {
+                    let error = BatchError { login: name, error: fetch_error_message(&e) };
+                    writeln!(out, "{}", serde_json::to_string(&error)?)?;
+                    failed.push(name.as_str());
+                }
+                Err(e) => {
+                    report_fetch_error(name, e);
+                    failed.push(name.as_str());
+                }
+            }
+        }
+        if !batch.is_empty() {
+            let profiles: Vec<_> = batch.iter().map(|(user, contributions)| (user, contributions)).collect();
+            match format {
+                OutputFormat::Csv => print_fields_delimited(&profiles, &args.fields, ',', &mut out)?,
+                OutputFormat::Tsv => print_fields_delimited(&profiles, &args.fields, '\t', &mut out)?,
+                _ => print_prometheus(&profiles, &mut out)?,
+            }
+        }
+        out.flush()?;
+        if !failed.is_empty() {
+            eprintln!("Error: {} of {} profiles could not be fetched: {}", failed.len(), usernames.len(), failed.join(", "));
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    
+    if args.all_time {
+        let user = match load_user_info(&api, &username, args.org, &token, cache_mode).await {
+            Ok(user) => user,
+            Err(e) => {
+                report_fetch_error(&username, FetchError::User(e));
+                std::process::exit(1);
+            }
+        };
+        match fetch_yearly_totals(&api, &user, &token, cache_mode).await {
+            Ok((totals, first_contribution)) => {
+                let term_width = render_options.width;
+                if render_options.show_header {
+                    draw_header(&user, None, None, term_width, &mut out)?;
+                }
+                draw_yearly_totals(&totals, first_contribution.as_deref(), term_width, &mut out)?;
+            }
+            Err(e) => {
+                report_fetch_error(&username, e);
+                std::process::exit(1);
+            }
+        }
+        out.flush()?;
+        return Ok(());
+    }
     
-    // Calculate available space for the calendar 
-    let calendar_width = std::cmp::min(53, term_width.saturating_sub(40) / 2);
+    if args.compare_period {
+        if format != OutputFormat::Terminal {
+            eprintln!("Error: --compare-period is only supported with the terminal format");
+            std::process::exit(1);
+        }
+        // GitHub's default window is the last year, so both windows are made explicit
+        let current = period.unwrap_or_else(|| last_days_period(MAX_SINCE_DAYS));
+        let one_year = Months::new(12);
+        let Some(previous) = current.from.checked_sub_months(one_year)
+            .zip(current.to.checked_sub_months(one_year))
+            .map(|(from, to)| DateRange { from, to })
+        else {
+            eprintln!("Error: the previous period is out of range");
+            std::process::exit(1);
+        };
+        
+        let (first, earlier) = tokio::join!(
+            fetch_profile(&api, &username, &token, Some(current), args.org, cache_mode),
+            load_user_contributions(&api, &username, &token, Some(previous), args.org, cache_mode),
+        );
+        let (user, contributions) = match first {
+            Ok(profile) => profile,
+            Err(e) => {
+                report_fetch_error(&username, e);
+                std::process::exit(1);
+            }
+        };
+        let earlier = match earlier {
+            Ok(contributions) => contributions,
+            Err(e) => {
+                report_fetch_error(&username, FetchError::Contributions(e));
+                std::process::exit(1);
+            }
+        };
+        
+        let (current_label, previous_label) = match (period, whole_year(current)) {
+            (None, _) => ("Last Year".to_string(), "Year Before".to_string()),
+            (Some(_), Some(year)) => (year.to_string(), (year - 1).to_string()),
+            (Some(_), None) => (format!("{} to {}", current.from, current.to), format!("{} to {}", previous.from, previous.to)),
+        };
+        let term_width = render_options.width;
+        if render_options.show_header {
+            draw_header(&user, None, None, term_width, &mut out)?;
+        }
+        draw_period_comparison(
+            (&current_label, &contributions.contribution_calendar),
+            (&previous_label, &earlier.contribution_calendar),
+            term_width,
+            &mut out,
+        )?;
+        out.flush()?;
+        return Ok(());
+    }
     
-    draw_header(user, term_width);
-    draw_contribution_calendar(calendar, calendar_width, term_width);
-    draw_statistics(calendar, term_width);
-}
-
-fn draw_header(user: &GitHubUser, term_width: usize) {
-    let title = format!(" {} ", user.login);
-    let padding = (term_width.saturating_sub(title.len())) / 2;
+    let first = fetch_profile(&api, &username, &token, period, args.org, cache_mode).await;
     
-    // Top border
-    println!("{}", "─".repeat(term_width).bright_blue());
+    if args.tui {
+        match first {
+            Ok((user, contributions)) => tui::run(&user, &contributions.contribution_calendar, &render_options)?,
+            Err(e) => {
+                report_fetch_error(&username, e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
     
-    // Title line
-    println!("{}{}{}", 
-        " ".repeat(padding), 
-        title.bright_white().bold(),
-        " ".repeat(term_width.saturating_sub(padding + title.len()))
-    );
+    let Some(other) = &args.compare else {
+        let mut result = first;
+        let mut first_round = true;
+        loop {
+            match result {
+                Ok((user, contributions)) => {
+                    if let Some(path) = &args.svg {
+                        let mut file = fs::File::create(path)?;
+                        render_svg(&contributions.contribution_calendar, &render_options.palette, &mut file)?;
+                    }
+                    if let Some(path) = &args.png {
+                        let file = io::BufWriter::new(fs::File::create(path)?);
+                        render_png(&contributions.contribution_calendar, &render_options.palette, args.cell_size, file)?;
+                    }
+                    if let Some(path) = &args.ical {
+                        let mut file = io::BufWriter::new(fs::File::create(path)?);
+                        render_ics(&user.login, &contributions.contribution_calendar, &mut file)?;
+                        file.flush()?;
+                    }
+                    if print_output {
+                        let sections = fetch_sections(&api, &args, &token, period, &user, &contributions).await;
+                        display_user_profile(&user, &contributions, &sections, format, &render_options, &mut out)?;
+                    }
+                }
+                Err(e) => {
+                    let is_user_error = matches!(e, FetchError::User(_));
+                    report_fetch_error(&username, e);
+                    // Once watching, a failed refresh is retried at the next tick
+                    if is_user_error && first_round {
+                        std::process::exit(1);
+                    }
+                }
+            }
+            
+            let Some(interval) = args.watch else {
+                break;
+            };
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+            
+            // Cached data would come back unchanged for an hour, so always refetch
+            let refresh_mode = if cache_mode == CacheMode::Off { CacheMode::Off } else { CacheMode::Refresh };
+            result = fetch_profile(&api, &username, &token, period, args.org, refresh_mode).await;
+            first_round = false;
+            // Clear the screen and move the cursor back to the top
+            print!("\x1b[2J\x1b[H");
+            io::stdout().flush()?;
+        }
+        out.flush()?;
+        return Ok(());
+    };
     
-    // User info section
-    let name = user.name.as_ref().unwrap_or(&user.login);
-    let info_line = format!("Name: {}  |  Repos: {}  |  Followers: {}  |  Following: {}", 
-        name, user.public_repos, user.followers, user.following);
+    let second = fetch_profile(&api, other, &token, period, args.org, cache_mode).await;
     
-    let info_padding = (term_width.saturating_sub(info_line.len())) / 2;
-    println!("{}{}", 
-        " ".repeat(info_padding),
-        info_line.bright_cyan()
-    );
+    // If only one of the two users could be fetched, still show that one
+    match (first, second) {
+        (Ok(left), Ok(right)) => draw_comparison(
+            (&left.0, &left.1.contribution_calendar),
+            (&right.0, &right.1.contribution_calendar),
+            &render_options,
+            &mut out,
+        )?,
+        (Ok((user, contributions)), Err(e)) => {
+            report_fetch_error(other, e);
+            display_user_profile(&user, &contributions, &ProfileSections::default(), format, &render_options, &mut out)?;
+        }
+        (Err(e), Ok((user, contributions))) => {
+            report_fetch_error(&username, e);
+            display_user_profile(&user, &contributions, &ProfileSections::default(), format, &render_options, &mut out)?;
+        }
+        (Err(first_err), Err(second_err)) => {
+            report_fetch_error(&username, first_err);
+            report_fetch_error(other, second_err);
+            std::process::exit(1);
+        }
+    }
     
-    println!("{}", "─".repeat(term_width).bright_blue());
+    out.flush()?;
+    Ok(())
+}
+
+fn parse_field(value: &str) -> Result<String, String> {
+    if FIELDS.iter().any(|(name, _)| *name == value) {
+        return Ok(value.to_string());
+    }
+    let valid: Vec<&str> = FIELDS.iter().map(|(name, _)| *name).collect();
+    Err(format!("unknown field, valid fields are: {}", valid.join(", ")))
+}
+
+fn parse_repo(value: &str) -> Result<(String, String), String> {
+    match value.split_once('/') {
+        Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => {
+            Ok((owner.to_string(), name.to_string()))
+        }
+        _ => Err("expected OWNER/NAME, e.g. rust-lang/rust".to_string()),
+    }
+}
+
+// The calendar keeps one column per day, so wide characters such as CJK
+// or emoji would break its alignment
+fn parse_empty_char(value: &str) -> Result<char, String> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.width() == Some(1) => Ok(c),
+        (Some(_), None) => Err("must be a character one column wide".to_string()),
+        _ => Err("must be exactly one character".to_string()),
+    }
+}
+
+// --token > --token-file > GITHUB_TOKEN > GH_TOKEN (the GitHub CLI's) > GITHUB_TOKEN_FILE
+// > the config file > `gh auth token`. The environment and gh are passed in so
+// the order can be tested without touching the real ones.
+fn resolve_token(
+    token: Option<String>,
+    token_file: Option<PathBuf>,
+    config_token: Option<String>,
+    var: impl Fn(&str) -> Option<std::ffi::OsString>,
+    gh_token: impl FnOnce() -> Option<String>,
+) -> Result<Option<String>, String> {
+    if token.is_some() {
+        return Ok(token);
+    }
+    if let Some(path) = token_file {
+        return read_token_file(&path).map(Some);
+    }
+    let text_var = |name| var(name).and_then(|value| value.into_string().ok());
+    if let Some(token) = text_var("GITHUB_TOKEN").or_else(|| text_var("GH_TOKEN")) {
+        return Ok(Some(token));
+    }
+    if let Some(path) = var("GITHUB_TOKEN_FILE") {
+        return read_token_file(&PathBuf::from(path)).map(Some);
+    }
+    Ok(config_token.or_else(gh_token))
+}
+
+// The token `gh auth login` stored, for people already using the GitHub CLI.
+// gh being missing or logged out just means there is no token.
+fn gh_cli_token() -> Option<String> {
+    let output = std::process::Command::new("gh")
+        .args(["auth", "token"])
+        .stderr(std::process::Stdio::null())
+        .output();
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            log::debug!("gh auth token exited with {}", output.status);
+            return None;
+        }
+        Err(e) => {
+            log::debug!("could not run gh: {}", e);
+            return None;
+        }
+    };
+    let token = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+fn load_app_key(path: &std::path::Path) -> Result<jsonwebtoken::EncodingKey, String> {
+    let pem = fs::read(path)
+        .map_err(|e| format!("could not read GitHub App key {}: {}", path.display(), e))?;
+    jsonwebtoken::EncodingKey::from_rsa_pem(&pem)
+        .map_err(|e| format!("GitHub App key {} is not an RSA private key in PEM format: {}", path.display(), e))
+}
+
+fn read_token_file(path: &std::path::Path) -> Result<String, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("could not read token file {}: {}", path.display(), e))?;
+    let token = contents.trim();
+    if token.is_empty() {
+        return Err(format!("token file {} is empty", path.display()));
+    }
+    Ok(token.to_string())
 }
 
-fn draw_contribution_calendar(calendar: &ContributionCalendar, calendar_width: usize, term_width: usize) {
-    let title = " GitHub Activity (Last Year) ";
-    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+// "in 25 minutes" for a quota reset given as a Unix timestamp
+fn reset_in_words(reset: i64) -> String {
+    let minutes = ((reset - chrono::Utc::now().timestamp()).max(0) + 59) / 60;
+    match minutes {
+        0 => "now".to_string(),
+        1 => "in 1 minute".to_string(),
+        _ => format!("in {} minutes", minutes),
+    }
+}
+
+fn print_token_help() {
+    eprintln!("You can:");
+    eprintln!("   1. Pass token with --token YOUR_TOKEN");
+    eprintln!("   2. Set GITHUB_TOKEN or GH_TOKEN environment variable (or --token-file / GITHUB_TOKEN_FILE)");
+    eprintln!("   3. Log in with the GitHub CLI: gh auth login");
+    eprintln!("   4. Create a token at: https://github.com/settings/tokens");
+    eprintln!("      (Required permissions: 'read:user' only)");
+}
+
+const ORG_PERIOD_UNSUPPORTED: &str = "--year, --since, --from and --to are not supported for organizations";
+
+// Longest --since window: 365 days ending today still fits in one year
+const MAX_SINCE_DAYS: u32 = 365;
+
+// The last `days` days, today included
+fn last_days_period(days: u32) -> DateRange {
+    let today = chrono::Utc::now().date_naive();
+    DateRange {
+        from: today - chrono::Days::new(u64::from(days) - 1),
+        to: today,
+    }
+}
+
+// --year or --from/--to as an inclusive range of days. A lone --from or --to
+// is extended to a one-year window, without going past today.
+fn resolve_period(
+    year: Option<i32>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<Option<DateRange>, String> {
+    let today = chrono::Utc::now().date_naive();
+    let one_year = Months::new(12);
+    let invalid = || "date is out of range".to_string();
     
-    println!("{}{}", " ".repeat(title_padding), title.bright_white().bold());
+    let range = match (year, from, to) {
+        (None, None, None) => return Ok(None),
+        (Some(year), _, _) => DateRange {
+            from: NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(invalid)?,
+            to: NaiveDate::from_ymd_opt(year, 12, 31).ok_or_else(invalid)?,
+        },
+        (None, Some(from), None) => {
+            let end = from.checked_add_months(one_year).and_then(|d| d.pred_opt()).ok_or_else(invalid)?;
+            DateRange { from, to: std::cmp::min(end, today) }
+        }
+        (None, None, Some(to)) => {
+            let start = to.checked_sub_months(one_year).and_then(|d| d.succ_opt()).ok_or_else(invalid)?;
+            DateRange { from: start, to }
+        }
+        (None, Some(from), Some(to)) => DateRange { from, to },
+    };
     
-    let total_text = format!("Total Contributions: {}", calendar.total_contributions);
-    let total_padding = (term_width.saturating_sub(total_text.len())) / 2;
-    println!("{}{}\n", " ".repeat(total_padding), total_text.bright_blue());
+    if range.from > range.to {
+        return Err(format!("start date {} is after end date {}", range.from, range.to));
+    }
+    // GitHub rejects contribution queries spanning more than one year
+    if range.from.checked_add_months(one_year).is_some_and(|limit| range.to >= limit) {
+        return Err(format!(
+            "{} to {} is longer than one year, which GitHub does not allow in a single query",
+            range.from, range.to
+        ));
+    }
+    Ok(Some(range))
+}
+
+async fn fetch_profile(
+    api: &Api,
+    username: &str,
+    token: &str,
+    period: Option<DateRange>,
+    org: bool,
+    cache_mode: CacheMode,
+) -> Result<(GitHubUser, ContributionsCollection), FetchError> {
+    if org && period.is_some() {
+        return Err(FetchError::Unsupported(ORG_PERIOD_UNSUPPORTED));
+    }
     
-    // Month headers
-    let months = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", 
-                  "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    // Both requests only need the name and token, so run them concurrently
+    let (user, contributions) = tokio::join!(
+        load_user_info(api, username, org, token, cache_mode),
+        load_user_contributions(api, username, token, period, org, cache_mode),
+    );
+    let user = user.map_err(FetchError::User)?;
     
-    let cal_padding = (term_width.saturating_sub(calendar_width + 8)) / 2;
-    print!("{}", " ".repeat(cal_padding));
-    print!("        ");
+    // The users endpoint also answers for organizations, flagging them by type;
+    // the calendar was then requested the wrong way and has to be fetched again
+    let contributions = if !org && user.account_type.as_deref() == Some("Organization") {
+        if period.is_some() {
+            return Err(FetchError::Unsupported(ORG_PERIOD_UNSUPPORTED));
+        }
+        load_user_contributions(api, username, token, period, true, cache_mode).await
+    } else {
+        contributions
+    };
+    let contributions = contributions.map_err(FetchError::Contributions)?;
     
-    let weeks_to_show = std::cmp::min(calendar.weeks.len(), calendar_width);
-    for i in 0..weeks_to_show {
-        if i % 4 == 0 && i / 4 < months.len() {
-            print!("{}", months[i / 4].bright_blue());
-        } else {
-            print!(" ");
+    Ok((user, contributions))
+}
+
+// The optional sections of a single profile, each fetched only on request.
+// A failure there only costs that section, not the whole profile.
+async fn fetch_sections(
+    api: &Api,
+    args: &Args,
+    token: &str,
+    period: Option<DateRange>,
+    user: &GitHubUser,
+    contributions: &ContributionsCollection,
+) -> ProfileSections {
+    let languages = if args.languages {
+        match get_top_languages(api, &user.login, token).await {
+            Ok(languages) => Some(languages),
+            Err(e) => {
+                eprintln!("Warning: could not retrieve languages: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let repos = if args.by_repo {
+        match get_repo_contributions(api, &user.login, token, period).await {
+            Ok(repos) => Some(repos),
+            Err(e) => {
+                eprintln!("Warning: could not retrieve contributions by repository: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let stars = if args.stars {
+        match get_total_stars(api, &user.login, token).await {
+            Ok(stars) => Some(stars),
+            Err(e) => {
+                eprintln!("Warning: could not count stars: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let delta = if args.snapshot {
+        match record_snapshot(user, contributions.contribution_calendar.total_contributions) {
+            Ok(delta) => delta,
+            Err(e) => {
+                eprintln!("Warning: could not save the snapshot: {}", e);
+                None
+            }
         }
+    } else {
+        None
+    };
+    ProfileSections { languages, repos, stars, delta }
+}
+
+// One contributions query per calendar year since the account was created,
+// the first one starting on the creation day itself, along with the date of
+// the first contribution ever. Past years rarely change, so they are usually
+// served from the cache.
+async fn fetch_yearly_totals(
+    api: &Api,
+    user: &GitHubUser,
+    token: &str,
+    cache_mode: CacheMode,
+) -> Result<(Vec<(i32, u32)>, Option<String>), FetchError> {
+    if user.account_type.as_deref() == Some("Organization") {
+        return Err(FetchError::Unsupported("--all-time is not supported for organizations"));
     }
-    println!();
+    // Entries cached before the creation date was recorded don't have it
+    let created = user.created_at.as_deref()
+        .and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+        .ok_or(FetchError::Unsupported("the account creation date is unknown, try again with --refresh"))?;
     
-    // Days of week labels
-    let weekdays = ["Mon", "Wed", "Fri"];
+    let today = chrono::Utc::now().date_naive();
+    let mut totals = Vec::new();
+    let mut first_contribution = None;
+    for year in created.year()..=today.year() {
+        if year > created.year() {
+            // Space the queries out to stay clear of GitHub's secondary rate limits
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+        let mut period = resolve_period(Some(year), None, None)
+            .map_err(|_| FetchError::Unsupported("the account creation date is out of range"))?;
+        // For an account created mid-year, the first window starts on the creation day
+        if year == created.year() {
+            period = period.map(|range| DateRange { from: created.date_naive(), ..range });
+        }
+        let contributions = load_user_contributions(api, &user.login, token, period, false, cache_mode)
+            .await
+            .map_err(FetchError::Contributions)?;
+        let calendar = &contributions.contribution_calendar;
+        if first_contribution.is_none() {
+            first_contribution = calendar.weeks.iter()
+                .flat_map(|w| &w.contribution_days)
+                .find(|d| d.contribution_count > 0)
+                .map(|d| d.date.clone());
+        }
+        totals.push((year, calendar.total_contributions));
+    }
+    Ok((totals, first_contribution))
+}
+
+fn report_fetch_error(username: &str, error: FetchError) {
+    match error {
+        FetchError::User(e) => {
+            eprintln!("Error: {}", e);
+            print_error_hint(&e);
+        }
+        FetchError::Contributions(e) => {
+            eprintln!("Error retrieving contributions for '{}': {}", username, e);
+            print_error_hint(&e);
+        }
+        FetchError::Unsupported(message) => {
+            eprintln!("Error retrieving contributions for '{}': {}", username, message);
+        }
+    }
+}
+
+fn fetch_error_message(error: &FetchError) -> String {
+    match error {
+        FetchError::User(e) => e.to_string(),
+        FetchError::Contributions(e) => format!("could not retrieve contributions: {}", e),
+        FetchError::Unsupported(message) => message.to_string(),
+    }
+}
+
+// Suggest what to do next depending on what went wrong
+fn print_error_hint(error: &GitStatError) {
+    match error {
+        GitStatError::NotFound { .. } => {}
+        GitStatError::Unauthorized(_) | GitStatError::GraphQl(_) => {
+            eprintln!("Please verify your token is valid and has proper permissions");
+        }
+        GitStatError::RateLimited { .. } => {
+            eprintln!("Wait for the limit to reset; cached results are used when available");
+        }
+        GitStatError::Timeout(_) | GitStatError::Network(_) => {
+            eprintln!("Check your network connection, or raise --timeout / --retries");
+        }
+        GitStatError::StatsPending(_) => {
+            eprintln!("Statistics are usually ready within a minute, please try again shortly");
+        }
+        GitStatError::Http(_) | GitStatError::Decode(_) => {
+            eprintln!("GitHub returned an unexpected response, please try again later");
+        }
+    }
+}
+
+// Space available for the calendar: every week, but only as much as the terminal allows
+fn calendar_width(calendar: &ContributionCalendar, term_width: usize) -> usize {
+    std::cmp::min(std::cmp::max(53, calendar.weeks.len()), term_width.saturating_sub(40) / 2)
+}
+
+fn display_user_profile(
+    user: &GitHubUser,
+    contributions: &ContributionsCollection,
+    sections: &ProfileSections,
+    format: OutputFormat,
+    options: &RenderOptions,
+    out: &mut impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let calendar = &contributions.contribution_calendar;
     
-    // Draw the calendar grid
-    for row in 0..7 {
-        print!("{}", " ".repeat(cal_padding));
-        
-        if row % 2 == 1 && row / 2 < weekdays.len() {
-            print!("{:>3} ", weekdays[row / 2].bright_blue());
-        } else {
-            print!("    ");
+    if !options.fields.is_empty() {
+        match format {
+            OutputFormat::Json => print_fields_json(user, contributions, &options.fields, options.json_pretty, out)?,
+            OutputFormat::Csv => print_fields_delimited(&[(user, contributions)], &options.fields, ',', out)?,
+            OutputFormat::Tsv => print_fields_delimited(&[(user, contributions)], &options.fields, '\t', out)?,
+            _ => {}
         }
-        
-        for week_idx in 0..weeks_to_show {
-            if week_idx < calendar.weeks.len() {
-                let week = &calendar.weeks[week_idx];
-                if let Some(day) = week.contribution_days.get(row) {
-                    let symbol = match day.contribution_count {
-                        0 => "■".truecolor(45, 51, 59),        
-                        1..=2 => "■".truecolor(14, 68, 121),   
-                        3..=5 => "■".truecolor(33, 110, 177),  
-                        6..=10 => "■".truecolor(52, 152, 219), 
-                        _ => "■".truecolor(116, 185, 255),     
-                    };
-                    print!("{}", symbol);
-                } else {
-                    print!(" ");
-                }
+        return Ok(());
+    }
+    
+    match format {
+        OutputFormat::Json => {
+            let report = UserReport {
+                login: &user.login,
+                name: user.name.as_deref(),
+                public_repos: user.public_repos,
+                followers: user.followers,
+                following: user.following,
+                calendar,
+                breakdown: &contributions.breakdown,
+                languages: sections.languages.as_deref(),
+                repositories: sections.repos.as_deref(),
+                statistics: report_statistics(calendar),
+                stars: sections.stars,
+            };
+            if options.json_pretty {
+                writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
             } else {
-                print!(" ");
+                writeln!(out, "{}", serde_json::to_string(&report)?)?;
             }
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            print_delimited(calendar, ',', out)?;
+            return Ok(());
         }
-        println!();
+        OutputFormat::Tsv => {
+            print_delimited(calendar, '\t', out)?;
+            return Ok(());
+        }
+        OutputFormat::Summary => {
+            print_summary(user, calendar, out)?;
+            return Ok(());
+        }
+        OutputFormat::Markdown => {
+            print_markdown(user, calendar, options, out)?;
+            return Ok(());
+        }
+        OutputFormat::Prometheus => {
+            print_prometheus(&[(user, contributions)], out)?;
+            return Ok(());
+        }
+        OutputFormat::Terminal => {}
     }
+
+    let term_width = options.width;
+    
+    let calendar_width = calendar_width(calendar, term_width);
     
-    // Legend with actual colors
-    let legend_padding = (term_width.saturating_sub(35)) / 2;
-    print!("\n{}   Less  ", " ".repeat(legend_padding));
-    print!("{}", "■".truecolor(45, 51, 59));        
-    print!("{}", "■".truecolor(14, 68, 121));      
-    print!("{}", "■".truecolor(33, 110, 177));     
-    print!("{}", "■".truecolor(52, 152, 219));      
-    print!("{}", "■".truecolor(116, 185, 255));     
-    println!("  More");
+    if options.show_header {
+        draw_header(user, sections.stars, sections.delta.as_ref(), term_width, out)?;
+    }
+    if options.show_calendar {
+        draw_contribution_calendar(calendar, calendar_width, term_width, options, out)?;
+    }
+    if options.sparkline {
+        draw_sparkline(calendar, term_width, out)?;
+    }
+    if let Some(window) = options.rolling {
+        draw_rolling(calendar, window, term_width, out)?;
+    }
+    if options.chart {
+        draw_braille_chart(calendar, term_width, out)?;
+    }
+    if options.monthly {
+        draw_monthly_table(calendar, term_width, out)?;
+    }
+    draw_contribution_breakdown(&contributions.breakdown, term_width, out)?;
+    draw_weekday_histogram(calendar, term_width, out)?;
+    if let Some(languages) = &sections.languages {
+        draw_languages(languages, options.color_mode, term_width, out)?;
+    }
+    if let Some(repos) = &sections.repos {
+        draw_repo_breakdown(repos, term_width, out)?;
+    }
+    if let Some(goal) = options.goal {
+        draw_goal(calendar.total_contributions, goal, term_width, out)?;
+    }
+    if options.show_stats && options.compact_stats {
+        draw_compact_statistics(calendar, term_width, out)?;
+    } else if options.show_stats {
+        draw_statistics(calendar, contributions.restricted_contributions, term_width, out)?;
+    }
+    Ok(())
 }
 
-fn draw_statistics(calendar: &ContributionCalendar, term_width: usize) {
-    let days_with_contributions = calendar.weeks.iter()
-        .flat_map(|w| &w.contribution_days)
-        .filter(|d| d.contribution_count > 0)
-        .count();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsString;
     
-    let max_contributions = calendar.weeks.iter()
-        .flat_map(|w| &w.contribution_days)
-        .map(|d| d.contribution_count)
-        .max()
-        .unwrap_or(0);
+    // An environment holding only `vars`
+    fn env_with(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<OsString> {
+        move |name| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| OsString::from(value))
+    }
     
-    let average = if days_with_contributions > 0 {
-        calendar.total_contributions as f32 / days_with_contributions as f32
-    } else {
-        0.0
-    };
+    // Runs `test` with a token file holding `contents`, deleting the file afterwards
+    fn with_token_file<T>(name: &str, contents: &str, test: impl FnOnce(PathBuf) -> T) -> T {
+        let path = env::temp_dir().join(format!("gitstat-test-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        let result = test(path.clone());
+        let _ = fs::remove_file(&path);
+        result
+    }
+    
+    fn gh() -> Option<String> {
+        Some("from-gh".to_string())
+    }
     
-    println!();
-    let stats_title = " Statistics ";
-    let stats_padding = (term_width.saturating_sub(stats_title.len())) / 2;
-    println!("{}{}", " ".repeat(stats_padding), stats_title.bright_white().bold());
+    const ALL_VARS: &[(&str, &str)] = &[("GITHUB_TOKEN", "from-github-token"), ("GH_TOKEN", "from-gh-token")];
     
-    let stats_line = format!("Active Days: {}  |  Max/Day: {}  |  Avg/Active Day: {:.1}", 
-        days_with_contributions, max_contributions, average);
-    let stats_line_padding = (term_width.saturating_sub(stats_line.len())) / 2;
-    println!("{}{}", " ".repeat(stats_line_padding), stats_line.bright_cyan());
+    #[test]
+    fn flag_comes_first() {
+        let token = with_token_file("flag", "from-file\n", |file| {
+            resolve_token(Some("from-flag".to_string()), Some(file), Some("from-config".to_string()), env_with(ALL_VARS), gh)
+        });
+        assert_eq!(token, Ok(Some("from-flag".to_string())));
+    }
+    
+    #[test]
+    fn token_file_comes_before_the_environment() {
+        let token = with_token_file("file", "  from-file\n", |file| resolve_token(None, Some(file), None, env_with(ALL_VARS), gh));
+        assert_eq!(token, Ok(Some("from-file".to_string())));
+    }
     
-    // Bottom border
-    println!("{}", "─".repeat(term_width).bright_blue());
+    #[test]
+    fn github_token_comes_before_gh_token() {
+        let token = resolve_token(None, None, Some("from-config".to_string()), env_with(ALL_VARS), gh);
+        assert_eq!(token, Ok(Some("from-github-token".to_string())));
+    }
+    
+    #[test]
+    fn gh_token_is_used_alone() {
+        let token = resolve_token(None, None, Some("from-config".to_string()), env_with(&[("GH_TOKEN", "from-gh-token")]), gh);
+        assert_eq!(token, Ok(Some("from-gh-token".to_string())));
+    }
+    
+    #[test]
+    fn token_file_variable_comes_after_gh_token() {
+        let (with_gh_token, without) = with_token_file("variable", "from-file-variable\n", |file| {
+            let var = |with_gh_token: bool| {
+                let file = file.clone();
+                move |name: &str| match name {
+                    "GITHUB_TOKEN_FILE" => Some(file.clone().into_os_string()),
+                    "GH_TOKEN" if with_gh_token => Some(OsString::from("from-gh-token")),
+                    _ => None,
+                }
+            };
+            (
+                resolve_token(None, None, None, var(true), gh),
+                resolve_token(None, None, Some("from-config".to_string()), var(false), gh),
+            )
+        });
+        assert_eq!(with_gh_token, Ok(Some("from-gh-token".to_string())));
+        assert_eq!(without, Ok(Some("from-file-variable".to_string())));
+    }
+    
+    #[test]
+    fn config_comes_before_the_gh_cli() {
+        let token = resolve_token(None, None, Some("from-config".to_string()), env_with(&[]), gh);
+        assert_eq!(token, Ok(Some("from-config".to_string())));
+    }
+    
+    #[test]
+    fn gh_cli_comes_last() {
+        assert_eq!(resolve_token(None, None, None, env_with(&[]), gh), Ok(Some("from-gh".to_string())));
+        assert_eq!(resolve_token(None, None, None, env_with(&[]), || None), Ok(None));
+    }
+    
+    #[test]
+    fn empty_token_file_is_an_error() {
+        let token = with_token_file("empty", "\n", |file| resolve_token(None, Some(file), None, env_with(&[]), gh));
+        assert!(token.is_err());
+    }
 }
@@ -0,0 +1,99 @@
+//! Data returned by the GitHub API.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Inclusive span of days covered by a contributions query.
+/// GitHub refuses spans longer than one year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GitHubUser {
+    pub login: String,
+    pub name: Option<String>,
+    pub public_repos: u32,
+    #[serde(default)]
+    pub followers: u32,
+    #[serde(default)]
+    pub following: u32,
+    // "User" or "Organization"
+    #[serde(rename = "type", default)]
+    pub account_type: Option<String>,
+    // ISO 8601 timestamp, e.g. "2011-01-25T18:44:36Z"
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ContributionsCollection {
+    #[serde(flatten)]
+    pub breakdown: ContributionBreakdown,
+    #[serde(rename = "contributionCalendar")]
+    pub contribution_calendar: ContributionCalendar,
+    // Private contributions counted in the calendar that the token can't see
+    // the details of; only non-zero for users sharing their private counts
+    #[serde(rename = "restrictedContributionsCount", default)]
+    pub restricted_contributions: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ContributionBreakdown {
+    #[serde(rename = "totalCommitContributions", default)]
+    pub commits: u32,
+    #[serde(rename = "totalPullRequestContributions", default)]
+    pub pull_requests: u32,
+    #[serde(rename = "totalIssueContributions", default)]
+    pub issues: u32,
+    #[serde(rename = "totalPullRequestReviewContributions", default)]
+    pub reviews: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ContributionCalendar {
+    #[serde(rename = "totalContributions")]
+    pub total_contributions: u32,
+    pub weeks: Vec<ContributionWeek>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ContributionWeek {
+    #[serde(rename = "contributionDays")]
+    pub contribution_days: Vec<ContributionDay>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ContributionDay {
+    pub date: String,
+    #[serde(rename = "contributionCount")]
+    pub contribution_count: u32,
+    pub color: String,
+}
+
+/// Request quota of one API (REST or GraphQL)
+#[derive(Debug, Deserialize)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    // Unix timestamp at which the quota is restored
+    pub reset: i64,
+}
+
+/// Commits made to one repository during the calendar's period
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RepoContributions {
+    pub name: String,
+    pub commits: u32,
+}
+
+/// Aggregated share of one language across the user's repositories
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LanguageStat {
+    pub name: String,
+    pub color: Option<String>,
+    pub bytes: u64,
+    pub percent: f64,
+}
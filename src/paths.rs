@@ -0,0 +1,40 @@
+//! Where gitstat keeps its files: the platform's config, cache and data
+//! directories (XDG on Linux, ~/Library on macOS, %APPDATA% on Windows).
+
+use directories::ProjectDirs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// Set by --cache-dir
+static CACHE_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "gitstat")
+}
+
+/// Makes `cache_dir` return `dir` for the rest of the run. Only the first
+/// call has an effect.
+pub fn set_cache_dir(dir: PathBuf) {
+    let _ = CACHE_DIR_OVERRIDE.set(dir);
+}
+
+/// $XDG_CONFIG_HOME/gitstat (~/.config/gitstat) on Linux,
+/// ~/Library/Application Support/gitstat on macOS, %APPDATA%\gitstat\config on Windows
+pub fn config_dir() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.config_dir().to_path_buf())
+}
+
+/// $XDG_CACHE_HOME/gitstat (~/.cache/gitstat) on Linux, ~/Library/Caches/gitstat
+/// on macOS, %LOCALAPPDATA%\gitstat\cache on Windows, unless --cache-dir is given
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = CACHE_DIR_OVERRIDE.get() {
+        return Some(dir.clone());
+    }
+    project_dirs().map(|dirs| dirs.cache_dir().to_path_buf())
+}
+
+/// $XDG_DATA_HOME/gitstat (~/.local/share/gitstat) on Linux,
+/// ~/Library/Application Support/gitstat on macOS, %APPDATA%\gitstat\data on Windows
+pub fn data_dir() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.data_dir().to_path_buf())
+}
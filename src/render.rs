@@ -0,0 +1,1459 @@
+//! Colored terminal rendering.
+
+use clap::ValueEnum;
+use colored::*;
+use std::io::{self, Write};
+use serde::Deserialize;
+use terminal_size::{Width, terminal_size};
+
+use crate::models::{
+    ContributionBreakdown, ContributionCalendar, ContributionDay, ContributionWeek, DateRange, GitHubUser, LanguageStat,
+    RepoContributions,
+};
+use crate::snapshot::SnapshotDelta;
+use crate::stats::{
+    active_day_percentile, average_per_active_day, best_day, compute_streaks, consistency_percent,
+    count_active_days, daily_std_dev, intensity_level, longest_gap, max_contributions_per_day,
+    monthly_totals, months_between, rolling_sums, trend_slope, week_month, weekday_totals, weekend_percent, whole_year, MONTHS,
+};
+
+/// How many colors the terminal can show
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ColorMode {
+    /// Detect from COLORTERM and TERM
+    Auto,
+    /// 24-bit colors
+    Truecolor,
+    /// The 256-color palette
+    #[value(name = "256")]
+    Ansi256,
+    /// The 16 basic colors; calendar squares are shaded as well
+    #[value(name = "16")]
+    Basic,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// GitHub's classic greens
+    GithubGreen,
+    /// Dark blue (default)
+    Blue,
+    /// Shades of gray
+    Grayscale,
+    /// Dracula's purples, ending in pink
+    Dracula,
+    /// Nord's frost blues
+    Nord,
+    /// Solarized dark, from blue to yellow
+    Solarized,
+    /// Blues into orange and yellow, for red-green (green-weak) color blindness
+    Deuteranopia,
+    /// Blues into pale yellow without any red, for red-weak color blindness
+    Protanopia,
+}
+
+/// Settings that change how the terminal output is drawn
+pub struct RenderOptions {
+    pub period: Option<DateRange>,
+    // Set when the period was given as a number of days back from today
+    pub last_days: Option<u32>,
+    pub palette: Palette,
+    pub color_mode: ColorMode,
+    pub ascii: bool,
+    pub emoji: bool,
+    // Shading glyphs on top of the colors, so the levels don't rely on hue alone
+    pub shaded: bool,
+    // Replaces the glyph of days without contributions
+    pub empty_char: Option<char>,
+    pub min_contributions: u32,
+    pub by_week: bool,
+    pub mini: bool,
+    // Exact counts in the daily grid instead of squares
+    pub numbers: bool,
+    // Continue the grid on further rows instead of leaving out weeks that don't fit
+    pub wrap: bool,
+    // Pause between weeks when revealing the daily grid, only set for terminals
+    pub animate: Option<std::time::Duration>,
+    pub monthly: bool,
+    pub goal: Option<u32>,
+    pub sparkline: bool,
+    // Braille line chart of the daily counts
+    pub chart: bool,
+    // Window in days of the rolling-sum chart, when shown
+    pub rolling: Option<usize>,
+    pub native_colors: bool,
+    // Blank cells on a background that brightens with the day's count
+    pub grayscale_bg: bool,
+    pub json_pretty: bool,
+    // Selected with --fields, empty for the full JSON/CSV output
+    pub fields: Vec<String>,
+    // The three main sections, each can be left out
+    pub show_header: bool,
+    pub show_calendar: bool,
+    pub show_stats: bool,
+    // The "Less ... More" key under the calendar
+    pub show_legend: bool,
+    // The Statistics section as one line
+    pub compact_stats: bool,
+    // Columns to lay the output out in, usually `terminal_width()`
+    pub width: usize,
+}
+
+/// "Last Year", "Last 90 days", a calendar year such as "2022", or
+/// "2022-01-01 to 2022-03-31"
+pub(crate) fn period_label(options: &RenderOptions) -> String {
+    if let Some(days) = options.last_days {
+        return format!("Last {} days", days);
+    }
+    match options.period {
+        None => "Last Year".to_string(),
+        Some(range) => match whole_year(range) {
+            Some(year) => year.to_string(),
+            None => format!("{} to {}", range.from, range.to),
+        },
+    }
+}
+
+/// Narrowest layout that still fits the labels and bars
+pub const MIN_WIDTH: usize = 40;
+
+pub fn terminal_width() -> usize {
+    if let Some((Width(w), _)) = terminal_size() {
+        w as usize
+    } else {
+        80
+    }
+}
+
+/// Runs a `draw_*` function against an in-memory buffer and returns what it
+/// drew, e.g. `render_to_string(|out| draw_statistics(&calendar, 0, 80, out))`.
+/// Combine with `colored::control::set_override(false)` for plain text.
+pub fn render_to_string(draw: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> io::Result<String> {
+    let mut buffer = Vec::new();
+    draw(&mut buffer)?;
+    String::from_utf8(buffer).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn draw_header<W: Write>(user: &GitHubUser, stars: Option<u64>, delta: Option<&SnapshotDelta>, term_width: usize, out: &mut W) -> io::Result<()> {
+    let title = format!(" {} ", user.login);
+    let padding = (term_width.saturating_sub(title.len())) / 2;
+    
+    // Top border
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    
+    // Title line
+    writeln!(out, "{}{}{}", 
+        " ".repeat(padding), 
+        title.bright_white().bold(),
+        " ".repeat(term_width.saturating_sub(padding + title.len()))
+    )?;
+    
+    // User info section
+    let name = user.name.as_ref().unwrap_or(&user.login);
+    let stars = stars.map(|stars| format!("  |  Stars: {}", stars)).unwrap_or_default();
+    let info_line = format!("Name: {}  |  Repos: {}{}  |  Followers: {}  |  Following: {}", 
+        name, user.public_repos, stars, user.followers, user.following);
+    
+    let info_padding = (term_width.saturating_sub(info_line.len())) / 2;
+    writeln!(out, "{}{}", 
+        " ".repeat(info_padding),
+        info_line.bright_cyan()
+    )?;
+    
+    if let Some(created) = user.created_at.as_deref().and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok()) {
+        let created = created.date_naive();
+        let age_line = format!("Member for {} (since {})",
+            age_in_words(months_between(created, chrono::Utc::now().date_naive())), created);
+        let age_padding = (term_width.saturating_sub(age_line.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(age_padding), age_line.bright_cyan())?;
+    }
+    
+    // Growth since the previous --snapshot run
+    if let Some(delta) = delta {
+        let delta_line = format!("Since {}:  Repos {:+}  |  Followers {:+}  |  Following {:+}  |  Contributions {:+}",
+            delta.since, delta.public_repos, delta.followers, delta.following, delta.total_contributions);
+        let delta_padding = (term_width.saturating_sub(delta_line.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(delta_padding), delta_line.bright_green())?;
+    }
+    
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    Ok(())
+}
+
+/// Title of the --repo view, in place of a profile header
+pub fn draw_repo_header<W: Write>(owner: &str, name: &str, term_width: usize, out: &mut W) -> io::Result<()> {
+    let title = format!(" {}/{} ", owner, name);
+    let padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    writeln!(out, "{}{}", " ".repeat(padding), title.bright_white().bold())?;
+    
+    let info_line = "Commits to the default branch over the last 52 weeks";
+    let info_padding = (term_width.saturating_sub(info_line.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(info_padding), info_line.bright_cyan())?;
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    Ok(())
+}
+
+pub fn draw_contribution_calendar<W: Write>(calendar: &ContributionCalendar, calendar_width: usize, term_width: usize, options: &RenderOptions, out: &mut W) -> io::Result<()> {
+    let title = format!(" GitHub Activity ({}) ", period_label(options));
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    let total_text = format!("Total Contributions: {}", calendar.total_contributions);
+    let total_padding = (term_width.saturating_sub(total_text.len())) / 2;
+    writeln!(out, "{}{}\n", " ".repeat(total_padding), total_text.bright_blue())?;
+    
+    // A grid of empty squares says nothing, e.g. for a brand-new account
+    let is_empty = calendar.total_contributions == 0
+        && calendar.weeks.iter().flat_map(|w| &w.contribution_days).all(|d| d.contribution_count == 0);
+    if is_empty {
+        let text = "No contributions in this period";
+        let padding = (term_width.saturating_sub(text.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(padding), text.bright_cyan())?;
+        return Ok(());
+    }
+    
+    if options.mini {
+        draw_mini_grid(calendar, term_width, options, out)?;
+    } else {
+        draw_full_grid(calendar, calendar_width, term_width, options, out)?;
+    }
+    
+    if !options.show_legend {
+        return Ok(());
+    }
+    
+    // The gradient runs from no contributions up to the busiest day
+    if gradient_shown(options) {
+        let max = max_contributions_per_day(calendar);
+        let steps = 8;
+        let legend_padding = (term_width.saturating_sub(steps * cell_width(options) + 14)) / 2;
+        write!(out, "\n{}0  ", " ".repeat(legend_padding))?;
+        for step in 0..steps as u32 {
+            write!(out, "{}", gradient_cell(step * max / (steps as u32 - 1), max, options))?;
+        }
+        writeln!(out, "  {}", max)?;
+        return Ok(());
+    }
+    
+    // Legend with actual colors
+    let legend_padding = (term_width.saturating_sub(35)) / 2;
+    write!(out, "\n{}   Less  ", " ".repeat(legend_padding))?;
+    for level in 0..options.palette.len() {
+        write!(out, "{}", level_cell(level, options))?;
+    }
+    writeln!(out, "  More")?;
+    Ok(())
+}
+
+fn draw_full_grid<W: Write>(calendar: &ContributionCalendar, calendar_width: usize, term_width: usize, options: &RenderOptions, out: &mut W) -> io::Result<()> {
+    let cal_padding = (term_width.saturating_sub(calendar_width + 8)) / 2;
+    let cell_width = cell_width(options);
+    let weeks_per_row = std::cmp::max(1, calendar_width / cell_width);
+    // Without --wrap, the weeks that don't fit are left out
+    let shown = if options.wrap {
+        &calendar.weeks[..]
+    } else {
+        &calendar.weeks[..std::cmp::min(calendar.weeks.len(), weeks_per_row)]
+    };
+    // Bars are scaled against the busiest week of every row
+    let max_week = week_totals(shown, options).into_iter().max().unwrap_or(0);
+    let max_day = max_contributions_per_day(calendar);
+    
+    for (idx, weeks) in shown.chunks(weeks_per_row).enumerate() {
+        if idx > 0 {
+            writeln!(out)?;
+        }
+        // Month headers, aligned with the week columns below
+        writeln!(out, "{}    {}", " ".repeat(cal_padding), month_labels(weeks, cell_width).bright_blue())?;
+        
+        if options.by_week {
+            write!(out, "{}    ", " ".repeat(cal_padding))?;
+            writeln!(out, "{}", weekly_row(weeks, max_week, options))?;
+        } else {
+            draw_daily_grid(weeks, max_day, cal_padding, options, out)?;
+        }
+    }
+    Ok(())
+}
+
+// Every two weeks share a column, so a full year fits in about 27 columns.
+// Days are summed per weekday and colored by the per-day average, which keeps
+// the shades comparable with the full grid. Labels are dropped to save width.
+fn draw_mini_grid<W: Write>(calendar: &ContributionCalendar, term_width: usize, options: &RenderOptions, out: &mut W) -> io::Result<()> {
+    let columns = calendar.weeks.len().div_ceil(2);
+    let padding = term_width.saturating_sub(columns * cell_width(options)) / 2;
+    
+    for row in 0..7 {
+        let mut line = String::new();
+        for pair in calendar.weeks.chunks(2) {
+            // The partial weeks at either end may not have this weekday
+            if pair.iter().all(|w| w.contribution_days.get(row).is_none()) {
+                line.push_str(&" ".repeat(cell_width(options)));
+                continue;
+            }
+            let sum: u32 = pair.iter()
+                .filter_map(|w| w.contribution_days.get(row))
+                .map(|d| d.contribution_count)
+                .filter(|&count| count >= options.min_contributions)
+                .sum();
+            let average = sum.div_ceil(pair.len() as u32);
+            if gradient_shown(options) {
+                line.push_str(&gradient_cell(average, max_contributions_per_day(calendar), options));
+            } else {
+                line.push_str(&level_cell(intensity_level(average), options));
+            }
+        }
+        writeln!(out, "{}{}", " ".repeat(padding), line)?;
+    }
+    Ok(())
+}
+
+fn draw_daily_grid<W: Write>(weeks: &[ContributionWeek], max_day: u32, cal_padding: usize, options: &RenderOptions, out: &mut W) -> io::Result<()> {
+    // --animate reveals one more week per frame, redrawing the rows in place
+    if let Some(delay) = options.animate {
+        for shown in 1..weeks.len() {
+            draw_daily_rows(&weeks[..shown], weeks.len(), max_day, cal_padding, options, out)?;
+            out.flush()?;
+            std::thread::sleep(delay);
+            // Back up to the first row
+            write!(out, "\x1b[7A")?;
+        }
+    }
+    draw_daily_rows(weeks, weeks.len(), max_day, cal_padding, options, out)
+}
+
+// The seven weekday rows, `weeks_to_show` cells wide
+fn draw_daily_rows<W: Write>(
+    weeks: &[ContributionWeek],
+    weeks_to_show: usize,
+    max_day: u32,
+    cal_padding: usize,
+    options: &RenderOptions,
+    out: &mut W,
+) -> io::Result<()> {
+    // Days of week labels
+    let weekdays = ["Mon", "Wed", "Fri"];
+    
+    // Draw the calendar grid
+    for row in 0..7 {
+        write!(out, "{}", " ".repeat(cal_padding))?;
+        
+        if row % 2 == 1 && row / 2 < weekdays.len() {
+            write!(out, "{:>3} ", weekdays[row / 2].bright_blue())?;
+        } else {
+            write!(out, "    ")?;
+        }
+        
+        writeln!(out, "{}", calendar_row(weeks, row, weeks_to_show, max_day, options))?;
+    }
+    Ok(())
+}
+
+// Month names placed above the first week of each month, skipping a label
+// when the previous one hasn't finished printing yet. Each week takes
+// `cell_width` columns.
+pub(crate) fn month_labels(weeks: &[ContributionWeek], cell_width: usize) -> String {
+    let mut line = String::new();
+    let mut last_month = None;
+    for (week_idx, week) in weeks.iter().enumerate() {
+        let month = week_month(week);
+        
+        if let Some(m) = month.filter(|_| month != last_month) {
+            last_month = month;
+            let column = line.chars().count();
+            let first_free = if column == 0 { 0 } else { column + 1 };
+            let target = week_idx * cell_width;
+            if target >= first_free && target + 3 <= weeks.len() * cell_width {
+                line.push_str(&" ".repeat(target - column));
+                line.push_str(MONTHS[m]);
+            }
+        }
+    }
+    line
+}
+
+// Weekly totals as a one-row bar chart: block height is scaled against the
+// busiest week and the color follows the daily intensity scale
+fn weekly_row(weeks: &[ContributionWeek], max: u32, options: &RenderOptions) -> String {
+    let bars: &[&str] = if options.ascii {
+        &LEVEL_ASCII_CHARS[1..]
+    } else {
+        &SPARK_BARS
+    };
+    
+    let mut line = String::new();
+    for total in week_totals(weeks, options) {
+        if total == 0 || max == 0 {
+            line.push_str(&level_cell(0, options));
+            continue;
+        }
+        let height = ((total as usize * bars.len()).div_ceil(max as usize)).clamp(1, bars.len());
+        let level = ((total as usize * 4).div_ceil(max as usize)).clamp(1, 4);
+        // Emoji have no heights, so their color alone carries the level
+        if options.emoji {
+            line.push_str(LEVEL_EMOJI[level]);
+            continue;
+        }
+        let bar = bars[height - 1];
+        if colored::control::SHOULD_COLORIZE.should_colorize() {
+            line.push_str(&paint_rgb(bar, options.palette[level], options.color_mode));
+        } else {
+            line.push_str(bar);
+        }
+    }
+    line
+}
+
+// Contributions per week, leaving out days under --min-contributions
+fn week_totals(weeks: &[ContributionWeek], options: &RenderOptions) -> Vec<u32> {
+    weeks.iter()
+        .map(|w| w.contribution_days.iter()
+            .map(|d| d.contribution_count)
+            .filter(|&count| count >= options.min_contributions)
+            .sum())
+        .collect()
+}
+
+// One weekday row of the grid, `weeks_to_show` cells wide. `max_day` is the
+// count the --grayscale-bg gradient tops out at.
+fn calendar_row(weeks: &[ContributionWeek], row: usize, weeks_to_show: usize, max_day: u32, options: &RenderOptions) -> String {
+    let mut line = String::new();
+    for week_idx in 0..weeks_to_show {
+        match weeks.get(week_idx).and_then(|w| w.contribution_days.get(row)) {
+            Some(day) => line.push_str(&day_cell(day, max_day, options)),
+            None => line.push_str(&" ".repeat(cell_width(options))),
+        }
+    }
+    line
+}
+
+pub fn draw_comparison<W: Write>(
+    left: (&GitHubUser, &ContributionCalendar),
+    right: (&GitHubUser, &ContributionCalendar),
+    options: &RenderOptions,
+    out: &mut W,
+) -> io::Result<()> {
+    let term_width = options.width;
+    let column_width = term_width / 2;
+    // Each column holds a 4-char weekday label followed by the weeks
+    let weeks = std::cmp::max(53, std::cmp::max(left.1.weeks.len(), right.1.weeks.len()));
+    // Both grids share one gradient so their shades compare
+    let max_day = std::cmp::max(max_contributions_per_day(left.1), max_contributions_per_day(right.1));
+    let weeks_to_show = std::cmp::min(weeks, column_width.saturating_sub(6) / cell_width(options));
+    let grid_width = weeks_to_show * cell_width(options) + 4;
+    let column_padding = column_width.saturating_sub(grid_width) / 2;
+    
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    
+    let title = format!(" {} vs {} ({}) ", left.0.login, right.0.login, period_label(options));
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    
+    // Prints one line made of a left and right column, each centered in its half
+    let print_columns = |out: &mut W, left_text: &str, right_text: &str, style: Paint| {
+        let left_padding = column_width.saturating_sub(left_text.chars().count()) / 2;
+        let right_padding = column_width.saturating_sub(right_text.chars().count()) / 2;
+        let gap = column_width.saturating_sub(left_padding + left_text.chars().count());
+        writeln!(out, "{}{}{}{}{}",
+            " ".repeat(left_padding),
+            style(left_text),
+            " ".repeat(gap),
+            " ".repeat(right_padding),
+            style(right_text)
+        )
+    };
+    
+    let name = |user: &GitHubUser| user.name.clone().unwrap_or_else(|| user.login.clone());
+    print_columns(out, &name(left.0), &name(right.0), |t| t.bright_white().bold())?;
+    writeln!(out)?;
+    
+    let weekdays = ["Mon", "Wed", "Fri"];
+    for row in 0..7 {
+        let label = if row % 2 == 1 && row / 2 < weekdays.len() {
+            format!("{:>3} ", weekdays[row / 2].bright_blue())
+        } else {
+            "    ".to_string()
+        };
+        let gap = column_width.saturating_sub(column_padding + grid_width);
+        writeln!(out, "{}{}{}{}{}{}",
+            " ".repeat(column_padding),
+            label,
+            calendar_row(&left.1.weeks, row, weeks_to_show, max_day, options),
+            " ".repeat(gap + column_padding),
+            label,
+            calendar_row(&right.1.weeks, row, weeks_to_show, max_day, options)
+        )?;
+    }
+    writeln!(out)?;
+    
+    let summary = |calendar: &ContributionCalendar| {
+        let (current_streak, longest_streak) = compute_streaks(calendar);
+        [
+            format!("Total Contributions: {}", calendar.total_contributions),
+            format!("Active Days: {}", count_active_days(calendar)),
+            format!("Current Streak: {}", current_streak),
+            format!("Longest Streak: {}", longest_streak),
+        ]
+    };
+    for (left_line, right_line) in summary(left.1).iter().zip(summary(right.1).iter()) {
+        print_columns(out, left_line, right_line, |t| t.bright_cyan())?;
+    }
+    
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    Ok(())
+}
+
+// Eight bar heights, lowest first
+const SPARK_BARS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+pub fn draw_sparkline<W: Write>(calendar: &ContributionCalendar, term_width: usize, out: &mut W) -> io::Result<()> {
+    writeln!(out)?;
+    let title = " Weekly Totals ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    // Keep the most recent weeks when they don't all fit
+    let totals: Vec<u32> = calendar.weeks.iter()
+        .map(|w| w.contribution_days.iter().map(|d| d.contribution_count).sum())
+        .collect();
+    let shown = &totals[totals.len().saturating_sub(term_width.saturating_sub(4))..];
+    let max = shown.iter().copied().max().unwrap_or(0);
+    
+    let line: String = shown.iter()
+        .map(|&total| {
+            let level = if max > 0 {
+                (total as usize * (SPARK_BARS.len() - 1) + max as usize / 2) / max as usize
+            } else {
+                0
+            };
+            SPARK_BARS[level]
+        })
+        .collect();
+    let padding = (term_width.saturating_sub(shown.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(padding), line.bright_cyan())?;
+    
+    let peak = format!("Busiest week: {} contributions", max);
+    let peak_padding = (term_width.saturating_sub(peak.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(peak_padding), peak.bright_blue())?;
+    Ok(())
+}
+
+// Rows of the rolling-sum chart; each row has eight steps
+const ROLLING_HEIGHT: usize = 5;
+
+pub fn draw_rolling<W: Write>(calendar: &ContributionCalendar, window: usize, term_width: usize, out: &mut W) -> io::Result<()> {
+    writeln!(out)?;
+    let title = format!(" Rolling {}-Day Total ", window);
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    let days: Vec<&str> = calendar.weeks.iter()
+        .flat_map(|w| &w.contribution_days)
+        .map(|d| d.date.as_str())
+        .collect();
+    let sums = rolling_sums(calendar, window);
+    if sums.is_empty() {
+        return Ok(());
+    }
+    
+    // One column per `step` days, showing the sum at the last of them
+    let max_columns = std::cmp::max(1, term_width.saturating_sub(10));
+    let step = sums.len().div_ceil(max_columns);
+    let columns: Vec<u32> = sums.chunks(step).map(|chunk| chunk[chunk.len() - 1]).collect();
+    let max = columns.iter().copied().max().unwrap_or(0);
+    
+    // axis label (4) + space + columns
+    let padding = (term_width.saturating_sub(5 + columns.len())) / 2;
+    for row in 0..ROLLING_HEIGHT {
+        // Eighths of a row already filled by the rows below this one
+        let base = (ROLLING_HEIGHT - 1 - row) * SPARK_BARS.len();
+        let line: String = columns.iter()
+            .map(|&sum| {
+                let eighths = if max > 0 {
+                    (sum as usize * ROLLING_HEIGHT * SPARK_BARS.len()).div_ceil(max as usize)
+                } else {
+                    0
+                };
+                match eighths.saturating_sub(base) {
+                    0 => " ",
+                    filled => SPARK_BARS[filled.min(SPARK_BARS.len()) - 1],
+                }
+            })
+            .collect();
+        let label = match row {
+            0 => max.to_string(),
+            _ if row == ROLLING_HEIGHT - 1 => "0".to_string(),
+            _ => String::new(),
+        };
+        writeln!(out, "{}{:>4} {}", " ".repeat(padding), label.bright_blue(), line.bright_cyan())?;
+    }
+    
+    // First and last dates under the ends of the chart
+    let first = days[0];
+    let last = days[days.len() - 1];
+    let gap = columns.len().saturating_sub(first.len() + last.len());
+    writeln!(out, "{}     {}{}{}", " ".repeat(padding), first.bright_blue(), " ".repeat(gap), last.bright_blue())?;
+    Ok(())
+}
+
+// Rows of the braille chart; each character is 2 dots wide and 4 tall
+const CHART_HEIGHT: usize = 4;
+
+// Bit of each dot in a braille character, by row then column
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+pub fn draw_braille_chart<W: Write>(calendar: &ContributionCalendar, term_width: usize, out: &mut W) -> io::Result<()> {
+    writeln!(out)?;
+    let title = " Daily Contributions ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    let days: Vec<&ContributionDay> = calendar.weeks.iter().flat_map(|w| &w.contribution_days).collect();
+    if days.is_empty() {
+        return Ok(());
+    }
+    
+    // Two points per character; when days outnumber points, each point
+    // keeps the busiest day of its stretch so spikes aren't averaged away
+    let max_columns = std::cmp::max(1, term_width.saturating_sub(10));
+    let points = std::cmp::min(days.len(), max_columns * 2);
+    let series: Vec<u32> = (0..points)
+        .map(|point| {
+            let start = point * days.len() / points;
+            let end = std::cmp::max(start + 1, (point + 1) * days.len() / points);
+            days[start..end].iter().map(|d| d.contribution_count).max().unwrap_or(0)
+        })
+        .collect();
+    let max = series.iter().copied().max().unwrap_or(0);
+    
+    // Dot height of each point, 0 at the bottom
+    let dot_rows = CHART_HEIGHT * 4;
+    let heights: Vec<usize> = series.iter()
+        .map(|&count| if max > 0 { (count as usize * (dot_rows - 1)).div_ceil(max as usize) } else { 0 })
+        .collect();
+    
+    let columns = points.div_ceil(2);
+    let mut cells = vec![vec![0u32; columns]; CHART_HEIGHT];
+    for (point, &height) in heights.iter().enumerate() {
+        // Join each point to the previous one so the line has no gaps
+        let previous = if point > 0 { heights[point - 1] } else { height };
+        for dot in std::cmp::min(previous, height)..=std::cmp::max(previous, height) {
+            let from_top = dot_rows - 1 - dot;
+            cells[from_top / 4][point / 2] |= BRAILLE_DOTS[from_top % 4][point % 2];
+        }
+    }
+    
+    // axis label (4) + space + columns
+    let padding = (term_width.saturating_sub(5 + columns)) / 2;
+    for (row, cells) in cells.iter().enumerate() {
+        let line: String = cells.iter()
+            .map(|&bits| char::from_u32(0x2800 + bits).unwrap_or(' '))
+            .collect();
+        let label = match row {
+            0 => max.to_string(),
+            _ if row == CHART_HEIGHT - 1 => "0".to_string(),
+            _ => String::new(),
+        };
+        writeln!(out, "{}{:>4} {}", " ".repeat(padding), label.bright_blue(), line.bright_cyan())?;
+    }
+    
+    // First and last dates under the ends of the chart
+    let first = &days[0].date;
+    let last = &days[days.len() - 1].date;
+    let gap = columns.saturating_sub(first.len() + last.len());
+    writeln!(out, "{}     {}{}{}", " ".repeat(padding), first.bright_blue(), " ".repeat(gap), last.bright_blue())?;
+    Ok(())
+}
+
+pub fn draw_monthly_table<W: Write>(calendar: &ContributionCalendar, term_width: usize, out: &mut W) -> io::Result<()> {
+    writeln!(out)?;
+    let title = " Monthly Totals ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    // "YYYY-MM" + gap + right-aligned total
+    let table_width = 7 + 4 + 8;
+    let padding = (term_width.saturating_sub(table_width)) / 2;
+    writeln!(out, "{}{:<7}    {:>8}", " ".repeat(padding), "Month".bright_blue(), "Total".bright_blue())?;
+    for (month, total) in monthly_totals(calendar) {
+        writeln!(out, "{}{:<7}    {:>8}", " ".repeat(padding), month, total.to_string().bright_cyan())?;
+    }
+    Ok(())
+}
+
+// "2 years, 3 months", "1 year", "5 months", or "less than a month" for new accounts
+fn age_in_words(months: u32) -> String {
+    let unit = |count: u32, name: &str| format!("{} {}{}", count, name, if count == 1 { "" } else { "s" });
+    match (months / 12, months % 12) {
+        (0, 0) => "less than a month".to_string(),
+        (0, months) => unit(months, "month"),
+        (years, 0) => unit(years, "year"),
+        (years, months) => format!("{}, {}", unit(years, "year"), unit(months, "month")),
+    }
+}
+
+/// One bar per year, then the all-time total and, when known, the date of the
+/// first contribution ever
+pub fn draw_yearly_totals<W: Write>(totals: &[(i32, u32)], first_contribution: Option<&str>, term_width: usize, out: &mut W) -> io::Result<()> {
+    writeln!(out)?;
+    let title = " Contributions per Year ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    let max = totals.iter().map(|(_, total)| *total).max().unwrap_or(0);
+    let bar_width = std::cmp::min(40, term_width.saturating_sub(24));
+    // year (4) + space + bar + space + total
+    let block_width = 4 + 1 + bar_width + 1 + 6;
+    let padding = (term_width.saturating_sub(block_width)) / 2;
+    
+    for (year, total) in totals {
+        let filled = if max > 0 {
+            (*total as usize * bar_width).div_ceil(max as usize)
+        } else {
+            0
+        };
+        writeln!(out, "{}{} {}{} {}",
+            " ".repeat(padding),
+            year.to_string().bright_blue(),
+            "█".repeat(filled).bright_cyan(),
+            " ".repeat(bar_width - filled),
+            total
+        )?;
+    }
+    
+    let grand_total: u32 = totals.iter().map(|(_, total)| total).sum();
+    let total_text = format!("All-time Contributions: {}", grand_total);
+    let total_padding = (term_width.saturating_sub(total_text.len())) / 2;
+    writeln!(out, "\n{}{}", " ".repeat(total_padding), total_text.bright_white().bold())?;
+    
+    if let Some(date) = first_contribution {
+        let first_text = format!("First contribution: {}", date);
+        let first_padding = (term_width.saturating_sub(first_text.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(first_padding), first_text.bright_cyan())?;
+    }
+    
+    // Bottom border
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    Ok(())
+}
+
+/// Year-over-year table of `--compare-period`: each window's totals, active
+/// days and streaks, then the change from the earlier window. A window's
+/// current streak is the one running on its last day.
+pub fn draw_period_comparison<W: Write>(
+    current: (&str, &ContributionCalendar),
+    previous: (&str, &ContributionCalendar),
+    term_width: usize,
+    out: &mut W,
+) -> io::Result<()> {
+    writeln!(out)?;
+    let title = " Period Comparison ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    let rows = |calendar: &ContributionCalendar| {
+        let (current_streak, longest_streak) = compute_streaks(calendar);
+        [calendar.total_contributions, count_active_days(calendar) as u32, current_streak, longest_streak]
+    };
+    let labels = ["Contributions", "Active Days", "Current Streak", "Longest Streak"];
+    let (now, before) = (rows(current.1), rows(previous.1));
+    
+    let column = std::cmp::max(10, std::cmp::max(current.0.len(), previous.0.len())) + 2;
+    // label (14) + both windows + change, e.g. "▲ +1234 (+100.0%)"
+    let table_width = 14 + 2 * column + 2 + 18;
+    let padding = (term_width.saturating_sub(table_width)) / 2;
+    writeln!(out, "{}{:<14}{}{}  {}",
+        " ".repeat(padding),
+        "",
+        format!("{:>width$}", previous.0, width = column).bright_blue(),
+        format!("{:>width$}", current.0, width = column).bright_blue(),
+        "Change".bright_blue()
+    )?;
+    for ((label, now), before) in labels.iter().zip(now).zip(before) {
+        let difference = now as i64 - before as i64;
+        let percent = if before > 0 {
+            format!("{:+.1}%", difference as f64 * 100.0 / before as f64)
+        } else {
+            "n/a".to_string()
+        };
+        let change = match difference.signum() {
+            1 => format!("▲ {:+} ({})", difference, percent).bright_green(),
+            -1 => format!("▼ {:+} ({})", difference, percent).bright_red(),
+            _ => format!("= 0 ({})", if before > 0 { "0.0%" } else { "n/a" }).normal(),
+        };
+        writeln!(out, "{}{:<14}{:>width$}{:>width$}  {}",
+            " ".repeat(padding),
+            label,
+            before,
+            now.to_string().bright_cyan(),
+            change,
+            width = column
+        )?;
+    }
+    
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    Ok(())
+}
+
+// Styling applied to a piece of text
+type Paint = fn(&str) -> ColoredString;
+
+pub fn draw_contribution_breakdown<W: Write>(breakdown: &ContributionBreakdown, term_width: usize, out: &mut W) -> io::Result<()> {
+    let rows: [(&str, u32, Paint); 4] = [
+        ("Commits", breakdown.commits, |s| s.bright_blue()),
+        ("Pull Requests", breakdown.pull_requests, |s| s.bright_cyan()),
+        ("Issues", breakdown.issues, |s| s.bright_magenta()),
+        ("Reviews", breakdown.reviews, |s| s.bright_green()),
+    ];
+    
+    let max = rows.iter().map(|(_, count, _)| *count).max().unwrap_or(0);
+    let bar_width = std::cmp::min(40, term_width.saturating_sub(30));
+    // label (13) + space + bar + space + count
+    let block_width = 13 + 1 + bar_width + 1 + 6;
+    let padding = (term_width.saturating_sub(block_width)) / 2;
+    
+    writeln!(out)?;
+    let title = " Contribution Types ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    for (label, count, paint) in rows {
+        let filled = if max > 0 {
+            (count as usize * bar_width).div_ceil(max as usize)
+        } else {
+            0
+        };
+        writeln!(out, "{}{:>13} {}{} {}",
+            " ".repeat(padding),
+            label.bright_blue(),
+            paint(&"█".repeat(filled)),
+            " ".repeat(bar_width - filled),
+            count
+        )?;
+    }
+    Ok(())
+}
+
+pub fn draw_weekday_histogram<W: Write>(calendar: &ContributionCalendar, term_width: usize, out: &mut W) -> io::Result<()> {
+    let names = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    let totals = weekday_totals(calendar);
+    let max = totals.iter().copied().max().unwrap_or(0);
+    let busiest = totals.iter().position(|&t| t == max && t > 0);
+    
+    writeln!(out)?;
+    let title = " Activity by Weekday ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    let bar_width = std::cmp::min(40, term_width.saturating_sub(20));
+    // label (3) + space + bar + space + count
+    let block_width = 3 + 1 + bar_width + 1 + 6;
+    let padding = (term_width.saturating_sub(block_width)) / 2;
+    
+    for (idx, (name, total)) in names.iter().zip(totals).enumerate() {
+        let filled = if max > 0 {
+            (total as usize * bar_width + max as usize / 2) / max as usize
+        } else {
+            0
+        };
+        let line = format!("{} {}{} {}", name, "█".repeat(filled), " ".repeat(bar_width - filled), total);
+        // Highlight the busiest weekday
+        if busiest == Some(idx) {
+            writeln!(out, "{}{}", " ".repeat(padding), line.bright_white().bold())?;
+        } else {
+            writeln!(out, "{}{}", " ".repeat(padding), line.bright_blue())?;
+        }
+    }
+    Ok(())
+}
+
+pub fn draw_languages<W: Write>(languages: &[LanguageStat], color_mode: ColorMode, term_width: usize, out: &mut W) -> io::Result<()> {
+    writeln!(out)?;
+    let title = " Top Languages ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    if languages.is_empty() {
+        let text = "No language data available";
+        let padding = (term_width.saturating_sub(text.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(padding), text.bright_cyan())?;
+        return Ok(());
+    }
+    
+    let bar_width = std::cmp::min(40, term_width.saturating_sub(32));
+    // label (14) + space + bar + space + percentage
+    let block_width = 14 + 1 + bar_width + 1 + 6;
+    let padding = (term_width.saturating_sub(block_width)) / 2;
+    
+    for language in languages {
+        let filled = ((language.percent / 100.0) * bar_width as f64).round() as usize;
+        let filled = std::cmp::min(filled, bar_width);
+        let bar = "█".repeat(filled);
+        // Use GitHub's language color when it has one
+        let bar = match language.color.as_deref().and_then(parse_hex_color) {
+            Some(color) if colored::control::SHOULD_COLORIZE.should_colorize() => paint_rgb(&bar, color, color_mode),
+            _ => bar.bright_blue().to_string(),
+        };
+        writeln!(out, "{}{:>14} {}{} {:>5.1}%",
+            " ".repeat(padding),
+            language.name.bright_blue(),
+            bar,
+            " ".repeat(bar_width - filled),
+            language.percent
+        )?;
+    }
+    Ok(())
+}
+
+pub fn draw_repo_breakdown<W: Write>(repos: &[RepoContributions], term_width: usize, out: &mut W) -> io::Result<()> {
+    writeln!(out)?;
+    let title = " Top Repositories ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    if repos.is_empty() {
+        let text = "No commits to any visible repository";
+        let padding = (term_width.saturating_sub(text.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(padding), text.bright_cyan())?;
+        return Ok(());
+    }
+    
+    // Long owner/name pairs are cut so the bars stay aligned
+    let label_width = repos.iter()
+        .map(|r| r.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(30);
+    let max = repos.iter().map(|r| r.commits).max().unwrap_or(0);
+    let bar_width = std::cmp::min(40, term_width.saturating_sub(label_width + 18));
+    // label + space + bar + space + count
+    let block_width = label_width + 1 + bar_width + 1 + 6;
+    let padding = (term_width.saturating_sub(block_width)) / 2;
+    
+    for repo in repos {
+        let filled = if max > 0 {
+            (repo.commits as usize * bar_width).div_ceil(max as usize)
+        } else {
+            0
+        };
+        let label: String = repo.name.chars().take(label_width).collect();
+        writeln!(out, "{}{:>width$} {}{} {}",
+            " ".repeat(padding),
+            label.bright_blue(),
+            "█".repeat(filled).bright_cyan(),
+            " ".repeat(bar_width - filled),
+            repo.commits,
+            width = label_width
+        )?;
+    }
+    Ok(())
+}
+
+/// Where a member stands on the `--org-members` leaderboard
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Standing {
+    Ranked(u32),
+    /// Contributions the token isn't allowed to see
+    Private,
+    /// Fetching the contributions failed, e.g. on a network error
+    Failed,
+}
+
+/// Members of `--org-members`, in the order given. Only ranked members get a
+/// rank and a bar; the others are labelled private or error.
+pub fn draw_leaderboard<W: Write>(
+    org: &str,
+    members: &[(String, Standing)],
+    term_width: usize,
+    options: &RenderOptions,
+    out: &mut W,
+) -> io::Result<()> {
+    let title = format!(" {}: Contributions ({}) ", org, period_label(options));
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    if members.is_empty() {
+        let text = "No public members";
+        let padding = (term_width.saturating_sub(text.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(padding), text.bright_cyan())?;
+        writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+        return Ok(());
+    }
+    
+    let label_width = members.iter()
+        .map(|(login, _)| login.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(30);
+    let rank_width = members.len().to_string().len();
+    let max = members.iter()
+        .filter_map(|(_, standing)| match standing {
+            Standing::Ranked(total) => Some(*total),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0);
+    let bar_width = std::cmp::min(40, term_width.saturating_sub(label_width + rank_width + 20));
+    // rank + ". " + label + space + bar + space + total
+    let block_width = rank_width + 2 + label_width + 1 + bar_width + 1 + 6;
+    let padding = (term_width.saturating_sub(block_width)) / 2;
+    
+    let mut rank = 0;
+    for (login, standing) in members {
+        let label: String = login.chars().take(label_width).collect();
+        let total = match standing {
+            Standing::Ranked(total) => total,
+            Standing::Private | Standing::Failed => {
+                let note = if *standing == Standing::Private {
+                    "private".bright_black()
+                } else {
+                    "error".bright_red()
+                };
+                writeln!(out, "{}{} {:<width$} {}",
+                    " ".repeat(padding),
+                    " ".repeat(rank_width + 1),
+                    label.bright_blue(),
+                    note,
+                    width = label_width
+                )?;
+                continue;
+            }
+        };
+        rank += 1;
+        let filled = if max > 0 {
+            (*total as usize * bar_width).div_ceil(max as usize)
+        } else {
+            0
+        };
+        writeln!(out, "{}{:>rank_width$}. {:<width$} {}{} {}",
+            " ".repeat(padding),
+            rank,
+            label.bright_blue(),
+            "█".repeat(filled).bright_cyan(),
+            " ".repeat(bar_width - filled),
+            total,
+            width = label_width
+        )?;
+    }
+    
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    Ok(())
+}
+
+// Parses "#rrggbb" into its components
+fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some((r, g, b))
+}
+
+/// Heatmap colors, from no contributions to the busiest days
+pub type Palette = [(u8, u8, u8); 5];
+
+pub fn palette_for(theme: Theme) -> Palette {
+    match theme {
+        Theme::GithubGreen => [
+            (22, 27, 34),
+            (14, 68, 41),
+            (0, 109, 50),
+            (38, 166, 65),
+            (57, 211, 83),
+        ],
+        Theme::Blue => [
+            (45, 51, 59),
+            (14, 68, 121),
+            (33, 110, 177),
+            (52, 152, 219),
+            (116, 185, 255),
+        ],
+        Theme::Grayscale => [
+            (40, 40, 40),
+            (90, 90, 90),
+            (140, 140, 140),
+            (190, 190, 190),
+            (240, 240, 240),
+        ],
+        Theme::Dracula => [
+            (68, 71, 90),
+            (98, 114, 164),
+            (139, 112, 210),
+            (189, 147, 249),
+            (255, 121, 198),
+        ],
+        Theme::Nord => [
+            (59, 66, 82),
+            (94, 129, 172),
+            (129, 161, 193),
+            (136, 192, 208),
+            (216, 222, 233),
+        ],
+        Theme::Solarized => [
+            (7, 54, 66),
+            (38, 139, 210),
+            (42, 161, 152),
+            (133, 153, 0),
+            (181, 137, 0),
+        ],
+        // Blue and yellow stay apart under every red-green deficiency, so the
+        // levels run from blue to yellow, each clearly lighter than the one before
+        // (the colors come from the Okabe-Ito set). Green-weak eyes still see
+        // orange, so it marks the fourth level.
+        Theme::Deuteranopia => [
+            (40, 42, 54),
+            (25, 60, 130),
+            (0, 114, 178),
+            (230, 159, 0),
+            (240, 228, 66),
+        ],
+        // Red-weak eyes see reds and oranges as dark, muddy shades that blend
+        // with the low levels, so this one stays on blues and yellows only
+        Theme::Protanopia => [
+            (40, 42, 54),
+            (0, 90, 160),
+            (86, 180, 233),
+            (240, 228, 66),
+            (255, 250, 200),
+        ],
+    }
+}
+
+// Without color every square would look the same, so use shading instead
+const LEVEL_PLAIN_CHARS: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+// Graded glyphs for terminals that can't display Unicode (`--ascii`)
+pub(crate) const LEVEL_ASCII_CHARS: [&str; 5] = [".", ":", "+", "*", "#"];
+
+// Colored squares for chat pastes; they are two columns wide in most fonts
+const LEVEL_EMOJI: [&str; 5] = ["⬛", "🟦", "🟩", "🟨", "🟥"];
+
+// Terminal columns taken by one calendar cell
+fn cell_width(options: &RenderOptions) -> usize {
+    if options.numbers {
+        3
+    } else if options.emoji {
+        2
+    } else {
+        1
+    }
+}
+
+// Intensity level of a calendar cell, treating days under --min-contributions as empty
+pub(crate) fn cell_level(count: u32, options: &RenderOptions) -> usize {
+    if count < options.min_contributions {
+        0
+    } else {
+        intensity_level(count)
+    }
+}
+
+fn level_cell(level: usize, options: &RenderOptions) -> String {
+    shaded_cell(level, options.palette[level], options)
+}
+
+// A day of the daily grid
+fn day_cell(day: &ContributionDay, max_day: u32, options: &RenderOptions) -> String {
+    if gradient_shown(options) {
+        let count = if day.contribution_count < options.min_contributions { 0 } else { day.contribution_count };
+        return gradient_cell(count, max_day, options);
+    }
+    if options.numbers {
+        return number_cell(day, options);
+    }
+    shaded_cell(cell_level(day.contribution_count, options), day_color(day, options), options)
+}
+
+// The day's count right-aligned after a space, still colored by level.
+// Three digits would run into the next cell, so those are capped.
+fn number_cell(day: &ContributionDay, options: &RenderOptions) -> String {
+    let count = match (day.contribution_count, options.empty_char) {
+        (0, Some(empty)) => empty.to_string(),
+        (count @ 0..=99, _) => count.to_string(),
+        _ => "++".to_string(),
+    };
+    let text = format!(" {:>2}", count);
+    if colored::control::SHOULD_COLORIZE.should_colorize() {
+        paint_rgb(&text, day_color(day, options), options.color_mode)
+    } else {
+        text
+    }
+}
+
+// Palette color for the day's level, or GitHub's own color with --native-colors
+pub(crate) fn day_color(day: &ContributionDay, options: &RenderOptions) -> (u8, u8, u8) {
+    let level = cell_level(day.contribution_count, options);
+    let native = if options.native_colors && day.contribution_count >= options.min_contributions {
+        parse_hex_color(&day.color)
+    } else {
+        None
+    };
+    native.unwrap_or(options.palette[level])
+}
+
+fn shaded_cell(level: usize, color: (u8, u8, u8), options: &RenderOptions) -> String {
+    if options.emoji {
+        return LEVEL_EMOJI[level].to_string();
+    }
+    let colorize = colored::control::SHOULD_COLORIZE.should_colorize();
+    // Sixteen colors can't tell the levels apart reliably, so shade them too
+    let mut buffer = [0; 4];
+    let glyph = match (options.ascii, colorize && options.color_mode != ColorMode::Basic) {
+        _ if level == 0 && options.empty_char.is_some() => options.empty_char.unwrap_or(' ').encode_utf8(&mut buffer),
+        (true, _) => LEVEL_ASCII_CHARS[level],
+        (false, true) if !options.shaded => "■",
+        (false, _) => LEVEL_PLAIN_CHARS[level],
+    };
+    
+    if colorize {
+        paint_rgb(glyph, color, options.color_mode)
+    } else {
+        glyph.to_string()
+    }
+}
+
+// Spaces can't show a background without colors, so the usual cells are drawn then
+fn gradient_shown(options: &RenderOptions) -> bool {
+    options.grayscale_bg && colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+// Darkest and brightest gray of the --grayscale-bg gradient
+const GRADIENT_LOW: u8 = 28;
+const GRADIENT_HIGH: u8 = 245;
+
+// A blank cell on a gray background. The square root spreads out the low
+// counts, which would otherwise all look alike next to one very busy day.
+fn gradient_cell(count: u32, max: u32, options: &RenderOptions) -> String {
+    let ratio = if max == 0 { 0.0 } else { (count.min(max) as f64 / max as f64).sqrt() };
+    let gray = GRADIENT_LOW + (ratio * (GRADIENT_HIGH - GRADIENT_LOW) as f64).round() as u8;
+    paint_bg_rgb(&" ".repeat(cell_width(options)), (gray, gray, gray), options.color_mode)
+}
+
+/// Colors `text` as closely as the terminal allows
+pub fn paint_rgb(text: &str, (r, g, b): (u8, u8, u8), mode: ColorMode) -> String {
+    match mode {
+        // Resolved by `detect_color_mode` before rendering
+        ColorMode::Auto | ColorMode::Truecolor => format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text),
+        ColorMode::Ansi256 => format!("\x1b[38;5;{}m{}\x1b[0m", ansi256((r, g, b)), text),
+        ColorMode::Basic => {
+            let index = ansi16((r, g, b));
+            let code = if index < 8 { 30 + index } else { 82 + index };
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        }
+    }
+}
+
+/// Color support advertised by the terminal: COLORTERM=truecolor (or 24bit)
+/// for 24-bit colors, a TERM such as xterm-256color for 256 colors
+pub fn detect_color_mode() -> ColorMode {
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default().to_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        ColorMode::Truecolor
+    } else if std::env::var("TERM").is_ok_and(|term| term.contains("256color")) {
+        ColorMode::Ansi256
+    } else {
+        ColorMode::Basic
+    }
+}
+
+/// Like `paint_rgb`, for the background
+pub fn paint_bg_rgb(text: &str, (r, g, b): (u8, u8, u8), mode: ColorMode) -> String {
+    match mode {
+        ColorMode::Auto | ColorMode::Truecolor => format!("\x1b[48;2;{};{};{}m{}\x1b[0m", r, g, b, text),
+        ColorMode::Ansi256 => format!("\x1b[48;5;{}m{}\x1b[0m", ansi256((r, g, b)), text),
+        ColorMode::Basic => {
+            let index = ansi16((r, g, b));
+            let code = if index < 8 { 40 + index } else { 92 + index };
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        }
+    }
+}
+
+// Index in the 256-color palette: the 6x6x6 cube, or the gray ramp for grays
+pub(crate) fn ansi256((r, g, b): (u8, u8, u8)) -> u8 {
+    if r == g && g == b {
+        return match r {
+            0..=7 => 16,
+            249..=255 => 231,
+            _ => 232 + ((r as u16 - 8) * 24 / 241) as u8,
+        };
+    }
+    let step = |c: u8| match c {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => ((c as u16 - 35) / 40) as u8,
+    };
+    16 + 36 * step(r) + 6 * step(g) + step(b)
+}
+
+// Nearest of the 16 basic colors (as a palette index), using xterm's defaults
+pub(crate) fn ansi16(color: (u8, u8, u8)) -> u8 {
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    let distance = |(r, g, b): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(r, color.0) + d(g, color.1) + d(b, color.2)
+    };
+    (0..16u8)
+        .min_by_key(|&index| distance(BASIC[index as usize]))
+        .unwrap_or(7)
+}
+
+pub fn draw_goal<W: Write>(total: u32, goal: u32, term_width: usize, out: &mut W) -> io::Result<()> {
+    let percent = total as f64 * 100.0 / goal as f64;
+    let bar_width = std::cmp::min(40, term_width.saturating_sub(20));
+    let filled = std::cmp::min(bar_width, (total as usize * bar_width) / goal as usize);
+    
+    writeln!(out)?;
+    let title = " Yearly Goal ";
+    let title_padding = (term_width.saturating_sub(title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(title_padding), title.bright_white().bold())?;
+    
+    let summary = format!("{} / {} contributions", total, goal);
+    let summary_padding = (term_width.saturating_sub(summary.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(summary_padding), summary.bright_cyan())?;
+    
+    // bar + space + percentage
+    let block_width = bar_width + 1 + 6;
+    let padding = (term_width.saturating_sub(block_width)) / 2;
+    if total >= goal {
+        // Goal reached: full bar in a celebratory color
+        writeln!(out, "{}{} {}",
+            " ".repeat(padding),
+            "█".repeat(bar_width).bright_yellow(),
+            format!("{:.0}%+", percent).bright_yellow().bold()
+        )?;
+    } else {
+        writeln!(out, "{}{}{} {:.0}%",
+            " ".repeat(padding),
+            "█".repeat(filled).bright_blue(),
+            "░".repeat(bar_width - filled).bright_black(),
+            percent
+        )?;
+    }
+    Ok(())
+}
+
+/// Active days, max and average per day and the streaks on one centered line.
+/// The last numbers are dropped when the line is wider than the terminal.
+pub fn draw_compact_statistics<W: Write>(calendar: &ContributionCalendar, term_width: usize, out: &mut W) -> io::Result<()> {
+    let (current_streak, longest_streak) = compute_streaks(calendar);
+    let mut parts = vec![
+        format!("Active Days: {}", count_active_days(calendar)),
+        format!("Max/Day: {}", max_contributions_per_day(calendar)),
+        format!("Avg/Active Day: {:.1}", average_per_active_day(calendar)),
+        format!("Current Streak: {}", current_streak),
+        format!("Longest Streak: {}", longest_streak),
+    ];
+    let mut line = parts.join("  |  ");
+    while line.len() > term_width && parts.len() > 1 {
+        parts.pop();
+        line = parts.join("  |  ");
+    }
+    if line.len() > term_width {
+        line = format!("{}…", &line[..term_width.saturating_sub(1)]);
+    }
+    
+    writeln!(out)?;
+    let padding = (term_width.saturating_sub(line.chars().count())) / 2;
+    writeln!(out, "{}{}", " ".repeat(padding), line.bright_cyan())?;
+    Ok(())
+}
+
+/// `restricted` is the number of private contributions included in the
+/// calendar whose details the token can't see
+pub fn draw_statistics<W: Write>(calendar: &ContributionCalendar, restricted: u32, term_width: usize, out: &mut W) -> io::Result<()> {
+    let days_with_contributions = count_active_days(calendar);
+    let max_contributions = max_contributions_per_day(calendar);
+    let average = average_per_active_day(calendar);
+    let median = active_day_percentile(calendar, 50);
+    let p90 = active_day_percentile(calendar, 90);
+    let consistency = consistency_percent(calendar);
+    let std_dev = daily_std_dev(calendar);
+    
+    let (current_streak, longest_streak) = compute_streaks(calendar);
+    let gap = longest_gap(calendar);
+    
+    writeln!(out)?;
+    let stats_title = " Statistics ";
+    let stats_padding = (term_width.saturating_sub(stats_title.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(stats_padding), stats_title.bright_white().bold())?;
+    
+    let stats_line = format!("Active Days: {}  |  Max/Day: {}  |  Avg/Active Day: {:.1}  |  Current Streak: {}  |  Longest Streak: {}  |  Longest Gap: {} days", 
+        days_with_contributions, max_contributions, average, current_streak, longest_streak, gap);
+    let stats_line_padding = (term_width.saturating_sub(stats_line.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(stats_line_padding), stats_line.bright_cyan())?;
+    
+    // Percentiles only look at days with at least one contribution
+    let percentile_line = format!("Median/Active Day: {}  |  90th Percentile/Active Day: {}", median, p90);
+    let percentile_padding = (term_width.saturating_sub(percentile_line.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(percentile_padding), percentile_line.bright_cyan())?;
+    
+    // Share of all days in the period, not just of active ones
+    let consistency_line = format!("Consistency: {:.0}% of days active  |  Std Dev/Day: {:.1}", consistency, std_dev);
+    let consistency_padding = (term_width.saturating_sub(consistency_line.len())) / 2;
+    writeln!(out, "{}{}", " ".repeat(consistency_padding), consistency_line.bright_cyan())?;
+    
+    if let Some(weekend) = weekend_percent(calendar) {
+        // Rounded once so the two shares always add up to 100%
+        let weekend = weekend.round();
+        let split_line = format!("Weekday: {:.0}%  |  Weekend: {:.0}%", 100.0 - weekend, weekend);
+        let split_padding = (term_width.saturating_sub(split_line.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(split_padding), split_line.bright_cyan())?;
+    }
+    
+    // Anything that rounds to +0.00 is shown as flat
+    let slope = trend_slope(calendar);
+    let (arrow, flat) = match format!("{:.2}", slope.abs()).as_str() {
+        "0.00" => ("▶".bright_black(), true),
+        _ if slope > 0.0 => ("▲".bright_green(), false),
+        _ => ("▼".bright_red(), false),
+    };
+    let trend_text = if flat { "Trend: 0.00/day".to_string() } else { format!("Trend: {:+.2}/day", slope) };
+    let trend_padding = (term_width.saturating_sub(trend_text.len() + 2)) / 2;
+    writeln!(out, "{}{} {}", " ".repeat(trend_padding), trend_text.bright_cyan(), arrow)?;
+    
+    if let Some((day, tie)) = best_day(calendar) {
+        let unit = if day.contribution_count == 1 { "contribution" } else { "contributions" };
+        let tie = if tie { " (tie)" } else { "" };
+        let best_line = format!("Best day: {} ({} {}){}", day.date, day.contribution_count, unit, tie);
+        let best_padding = (term_width.saturating_sub(best_line.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(best_padding), best_line.bright_cyan())?;
+    }
+    
+    // A total that looks low for the activity shown is usually explained here
+    if restricted > 0 {
+        let unit = if restricted == 1 { "contribution" } else { "contributions" };
+        let private_line = format!("{} private {} hidden (counted, but without details)", restricted, unit);
+        let private_padding = (term_width.saturating_sub(private_line.len())) / 2;
+        writeln!(out, "{}{}", " ".repeat(private_padding), private_line.bright_black())?;
+    }
+    
+    // Bottom border
+    writeln!(out, "{}", "─".repeat(term_width).bright_blue())?;
+    Ok(())
+}
@@ -0,0 +1,172 @@
+//! Offline checks behind `--selftest`, to confirm a build works without a token or network.
+
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use colored::Colorize;
+
+use crate::config::Config;
+use crate::models::{ContributionCalendar, ContributionDay, ContributionWeek, DateRange};
+use crate::render::{
+    draw_contribution_calendar, draw_statistics, palette_for, render_to_string, ColorMode,
+    RenderOptions, Theme,
+};
+use crate::stats::{compute_streaks, weekday_totals, whole_year};
+
+// A check's name and the test itself, which explains what went wrong on failure
+type Check = (&'static str, fn() -> Result<(), String>);
+
+const CHECKS: [Check; 4] = [
+    ("config parsing", check_config),
+    ("palette lookups", check_palettes),
+    ("date parsing", check_dates),
+    ("calendar rendering", check_rendering),
+];
+
+// Three weeks from a Sunday, across the 2024 leap day
+const FIXTURE_START: &str = "2024-02-25";
+const FIXTURE_COUNTS: [u32; 21] = [0, 1, 3, 0, 6, 2, 0, 4, 4, 11, 0, 0, 1, 2, 5, 3, 0, 1, 7, 2, 9];
+
+/// Runs every check, printing one PASS or FAIL line each, and returns whether all passed
+pub fn run<W: Write>(out: &mut W) -> io::Result<bool> {
+    let mut passed = true;
+    for (name, check) in CHECKS {
+        match check() {
+            Ok(()) => writeln!(out, "{} {}", "PASS".bright_green().bold(), name)?,
+            Err(reason) => {
+                writeln!(out, "{} {}: {}", "FAIL".bright_red().bold(), name, reason)?;
+                passed = false;
+            }
+        }
+    }
+    Ok(passed)
+}
+
+fn check_config() -> Result<(), String> {
+    let config: Config = toml::from_str("theme = \"nord\"\nformat = \"json\"\ntimeout = 5\n")
+        .map_err(|e| format!("sample config rejected: {}", e))?;
+    if config.theme != Some(Theme::Nord) || config.timeout != Some(5) || config.token.is_some() {
+        return Err(format!("sample config read as {:?}", config));
+    }
+    if toml::from_str::<Config>("colour = \"blue\"\n").is_ok() {
+        return Err("an unknown key was accepted".to_string());
+    }
+    Ok(())
+}
+
+fn check_palettes() -> Result<(), String> {
+    for theme in Theme::value_variants() {
+        let name = theme.to_possible_value().map(|value| value.get_name().to_string()).unwrap_or_default();
+        if Theme::from_str(&name, false).ok() != Some(*theme) {
+            return Err(format!("theme '{}' can't be selected by name", name));
+        }
+        let palette = palette_for(*theme);
+        if palette.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(format!("theme '{}' repeats a color between two levels", name));
+        }
+    }
+    Ok(())
+}
+
+fn check_dates() -> Result<(), String> {
+    let calendar = fixture_calendar()?;
+    let days: Vec<&ContributionDay> = calendar.weeks.iter().flat_map(|w| &w.contribution_days).collect();
+    if !days.iter().any(|day| day.date == "2024-02-29") {
+        return Err("the leap day is missing from the fixture".to_string());
+    }
+    // Monday first: the fixture's Sundays hold 0, 4 and 5 contributions
+    let totals = weekday_totals(&calendar);
+    if totals[6] != 9 || totals.iter().sum::<u32>() != calendar.total_contributions {
+        return Err(format!("weekday totals came out as {:?}", totals));
+    }
+    let range = DateRange {
+        from: parse_date("2024-01-01")?,
+        to: parse_date("2024-12-31")?,
+    };
+    if whole_year(range) != Some(2024) {
+        return Err("2024-01-01 to 2024-12-31 was not recognized as 2024".to_string());
+    }
+    Ok(())
+}
+
+fn check_rendering() -> Result<(), String> {
+    let calendar = fixture_calendar()?;
+    let options = fixture_options();
+    let grid = render_to_string(|out| draw_contribution_calendar(&calendar, 53, 80, &options, out))
+        .map_err(|e| format!("could not draw the calendar: {}", e))?;
+    let total = format!("Total Contributions: {}", calendar.total_contributions);
+    if !grid.contains(&total) || !grid.contains("Mon") || !grid.contains("Less") {
+        return Err("the calendar is missing its total, weekday labels or legend".to_string());
+    }
+    let statistics = render_to_string(|out| draw_statistics(&calendar, 0, 80, out))
+        .map_err(|e| format!("could not draw the statistics: {}", e))?;
+    let (_, longest_streak) = compute_streaks(&calendar);
+    if !statistics.contains(&format!("Longest Streak: {}", longest_streak)) {
+        return Err("the statistics don't show the longest streak".to_string());
+    }
+    Ok(())
+}
+
+fn parse_date(date: &str) -> Result<chrono::NaiveDate, String> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("could not parse {}: {}", date, e))
+}
+
+fn fixture_calendar() -> Result<ContributionCalendar, String> {
+    let start = parse_date(FIXTURE_START)?;
+    let days: Vec<ContributionDay> = FIXTURE_COUNTS
+        .iter()
+        .enumerate()
+        .map(|(offset, &count)| ContributionDay {
+            date: (start + chrono::Days::new(offset as u64)).format("%Y-%m-%d").to_string(),
+            contribution_count: count,
+            color: "#ebedf0".to_string(),
+        })
+        .collect();
+    // Weeks start on Sunday, like GitHub's
+    for day in days.iter().step_by(7) {
+        if parse_date(&day.date)?.format("%a").to_string() != "Sun" {
+            return Err(format!("week starting {} doesn't start on a Sunday", day.date));
+        }
+    }
+    Ok(ContributionCalendar {
+        total_contributions: FIXTURE_COUNTS.iter().sum(),
+        weeks: days
+            .chunks(7)
+            .map(|week| ContributionWeek { contribution_days: week.to_vec() })
+            .collect(),
+    })
+}
+
+fn fixture_options() -> RenderOptions {
+    RenderOptions {
+        period: None,
+        last_days: None,
+        palette: palette_for(Theme::Blue),
+        color_mode: ColorMode::Truecolor,
+        ascii: false,
+        emoji: false,
+        shaded: false,
+        empty_char: None,
+        min_contributions: 0,
+        by_week: false,
+        mini: false,
+        numbers: false,
+        wrap: false,
+        animate: None,
+        monthly: false,
+        goal: None,
+        sparkline: false,
+        chart: false,
+        rolling: None,
+        native_colors: false,
+        grayscale_bg: false,
+        json_pretty: false,
+        fields: Vec::new(),
+        show_header: true,
+        show_calendar: true,
+        show_stats: true,
+        show_legend: true,
+        compact_stats: false,
+        width: 80,
+    }
+}
@@ -0,0 +1,82 @@
+//! Local history of profile counts, used to show growth between runs.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::models::GitHubUser;
+use crate::paths;
+
+/// Counts recorded for one user on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub public_repos: u32,
+    pub followers: u32,
+    pub following: u32,
+    pub total_contributions: u32,
+}
+
+/// Change between an earlier snapshot and the current counts
+#[derive(Debug)]
+pub struct SnapshotDelta {
+    // Date of the earlier snapshot, YYYY-MM-DD
+    pub since: String,
+    pub public_repos: i64,
+    pub followers: i64,
+    pub following: i64,
+    pub total_contributions: i64,
+}
+
+// login -> date (YYYY-MM-DD) -> counts
+type History = BTreeMap<String, BTreeMap<String, Snapshot>>;
+
+/// history.json in the platform's data directory, e.g. ~/.local/share/gitstat/history.json
+pub fn history_path() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join("history.json"))
+}
+
+/// Saves today's counts for `user` and returns the change since the most
+/// recent snapshot taken on an earlier day. The first run only records a
+/// baseline and returns `None`. Running again on the same day replaces
+/// today's entry.
+pub fn record_snapshot(user: &GitHubUser, total_contributions: u32) -> io::Result<Option<SnapshotDelta>> {
+    let path = history_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory to store the history in"))?;
+    
+    // A history that can't be parsed is reported rather than overwritten
+    let mut history: History = match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => History::new(),
+        Err(e) => return Err(e),
+    };
+    
+    let today = chrono::Local::now().date_naive().to_string();
+    let current = Snapshot {
+        public_repos: user.public_repos,
+        followers: user.followers,
+        following: user.following,
+        total_contributions,
+    };
+    
+    let entries = history.entry(user.login.to_lowercase()).or_default();
+    let delta = entries.range(..today.clone()).next_back().map(|(date, previous)| SnapshotDelta {
+        since: date.clone(),
+        public_repos: current.public_repos as i64 - previous.public_repos as i64,
+        followers: current.followers as i64 - previous.followers as i64,
+        following: current.following as i64 - previous.following as i64,
+        total_contributions: current.total_contributions as i64 - previous.total_contributions as i64,
+    });
+    entries.insert(today, current);
+    
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = serde_json::to_string_pretty(&history)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(&path, contents)?;
+    
+    Ok(delta)
+}
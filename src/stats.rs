@@ -0,0 +1,342 @@
+//! Numbers derived from a contribution calendar.
+
+use chrono::Datelike;
+
+use crate::models::{ContributionCalendar, ContributionDay, ContributionWeek, DateRange};
+
+pub const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun",
+                            "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// Zero-based month of the first day of the week
+pub fn week_month(week: &ContributionWeek) -> Option<usize> {
+    week.contribution_days.first()
+        .and_then(|d| chrono::NaiveDate::parse_from_str(&d.date, "%Y-%m-%d").ok())
+        .map(|d| d.month0() as usize)
+}
+
+/// Sums contributions per "YYYY-MM" month, filling in empty months so the
+/// series has no gaps
+pub fn monthly_totals(calendar: &ContributionCalendar) -> Vec<(String, u32)> {
+    let mut by_month: std::collections::BTreeMap<&str, u32> = std::collections::BTreeMap::new();
+    for day in calendar.weeks.iter().flat_map(|w| &w.contribution_days) {
+        if let Some(month) = day.date.get(..7) {
+            *by_month.entry(month).or_insert(0) += day.contribution_count;
+        }
+    }
+    
+    let (Some(first), Some(last)) = (by_month.keys().next(), by_month.keys().next_back()) else {
+        return Vec::new();
+    };
+    let parse = |month: &str| -> Option<(i32, u32)> {
+        let (y, m) = month.split_once('-')?;
+        Some((y.parse().ok()?, m.parse().ok()?))
+    };
+    let (Some((mut year, mut month)), Some(end)) = (parse(first), parse(last)) else {
+        return by_month.into_iter().map(|(m, t)| (m.to_string(), t)).collect();
+    };
+    
+    let mut totals = Vec::new();
+    while (year, month) <= end {
+        let key = format!("{:04}-{:02}", year, month);
+        let total = by_month.get(key.as_str()).copied().unwrap_or(0);
+        totals.push((key, total));
+        if month == 12 {
+            year += 1;
+            month = 1;
+        } else {
+            month += 1;
+        }
+    }
+    totals
+}
+
+/// Sums contributions per day of the week, Monday first
+pub fn weekday_totals(calendar: &ContributionCalendar) -> [u32; 7] {
+    let mut totals = [0; 7];
+    for day in calendar.weeks.iter().flat_map(|w| &w.contribution_days) {
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") {
+            totals[date.weekday().num_days_from_monday() as usize] += day.contribution_count;
+        }
+    }
+    totals
+}
+
+/// Percentage of the contributions made on Saturdays and Sundays, `None` without any
+pub fn weekend_percent(calendar: &ContributionCalendar) -> Option<f32> {
+    let totals = weekday_totals(calendar);
+    let total: u32 = totals.iter().sum();
+    (total > 0).then(|| (totals[5] + totals[6]) as f32 * 100.0 / total as f32)
+}
+
+pub fn intensity_level(count: u32) -> usize {
+    match count {
+        0 => 0,
+        1..=2 => 1,
+        3..=5 => 2,
+        6..=10 => 3,
+        _ => 4,
+    }
+}
+
+pub fn count_active_days(calendar: &ContributionCalendar) -> usize {
+    calendar.weeks.iter()
+        .flat_map(|w| &w.contribution_days)
+        .filter(|d| d.contribution_count > 0)
+        .count()
+}
+
+pub fn max_contributions_per_day(calendar: &ContributionCalendar) -> u32 {
+    calendar.weeks.iter()
+        .flat_map(|w| &w.contribution_days)
+        .map(|d| d.contribution_count)
+        .max()
+        .unwrap_or(0)
+}
+
+/// The day with the most contributions, and whether other days tie with it.
+/// The earliest of tied days is returned; `None` without any contribution.
+pub fn best_day(calendar: &ContributionCalendar) -> Option<(&ContributionDay, bool)> {
+    let mut best: Option<(&ContributionDay, bool)> = None;
+    for day in calendar.weeks.iter().flat_map(|w| &w.contribution_days) {
+        match best {
+            _ if day.contribution_count == 0 => {}
+            Some((top, _)) if day.contribution_count < top.contribution_count => {}
+            Some((top, _)) if day.contribution_count == top.contribution_count => {
+                let earliest = if day.date < top.date { day } else { top };
+                best = Some((earliest, true));
+            }
+            _ => best = Some((day, false)),
+        }
+    }
+    best
+}
+
+pub fn average_per_active_day(calendar: &ContributionCalendar) -> f32 {
+    let days_with_contributions = count_active_days(calendar);
+    if days_with_contributions > 0 {
+        calendar.total_contributions as f32 / days_with_contributions as f32
+    } else {
+        0.0
+    }
+}
+
+/// Sum of the last `window` days for every day of the calendar, in order.
+/// Until `window` days have passed the sums only cover the days so far.
+pub fn rolling_sums(calendar: &ContributionCalendar, window: usize) -> Vec<u32> {
+    let counts: Vec<u32> = calendar.weeks.iter()
+        .flat_map(|w| &w.contribution_days)
+        .map(|d| d.contribution_count)
+        .collect();
+    let mut sums = Vec::with_capacity(counts.len());
+    let mut sum = 0;
+    for (idx, count) in counts.iter().enumerate() {
+        sum += count;
+        if idx >= window {
+            sum -= counts[idx - window];
+        }
+        sums.push(sum);
+    }
+    sums
+}
+
+// The calendar's days up to today. GitHub can pad the last week with days
+// still to come, which would end every streak and lengthen the trailing gap.
+fn elapsed_days(calendar: &ContributionCalendar) -> impl Iterator<Item = &ContributionDay> {
+    let today = chrono::Utc::now().date_naive().format("%Y-%m-%d").to_string();
+    calendar.weeks.iter()
+        .flat_map(|w| &w.contribution_days)
+        .filter(move |d| d.date <= today)
+}
+
+/// Share of days with at least one contribution, as a percentage. The
+/// denominator is the number of days the calendar actually contains up to
+/// today (not 365), so custom ranges, leap years and partial weeks are scored fairly.
+pub fn consistency_percent(calendar: &ContributionCalendar) -> f32 {
+    let total_days = elapsed_days(calendar).count();
+    if total_days > 0 {
+        count_active_days(calendar) as f32 * 100.0 / total_days as f32
+    } else {
+        0.0
+    }
+}
+
+/// Population standard deviation of the daily counts, empty days up to today included
+pub fn daily_std_dev(calendar: &ContributionCalendar) -> f32 {
+    let counts: Vec<f32> = elapsed_days(calendar)
+        .map(|d| d.contribution_count as f32)
+        .collect();
+    if counts.is_empty() {
+        return 0.0;
+    }
+    let mean = counts.iter().sum::<f32>() / counts.len() as f32;
+    let variance = counts.iter().map(|count| (count - mean).powi(2)).sum::<f32>() / counts.len() as f32;
+    variance.sqrt()
+}
+
+/// Least-squares slope of the daily counts over time, in contributions per
+/// day: positive when activity is picking up. 0 with fewer than two days.
+pub fn trend_slope(calendar: &ContributionCalendar) -> f32 {
+    let counts: Vec<f64> = elapsed_days(calendar).map(|d| d.contribution_count as f64).collect();
+    let n = counts.len() as f64;
+    if counts.len() < 2 {
+        return 0.0;
+    }
+    // Days are evenly spaced, so their index stands in for the date
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = counts.iter().sum::<f64>() / n;
+    let (mut covariance, mut variance) = (0.0, 0.0);
+    for (x, y) in counts.iter().enumerate() {
+        let dx = x as f64 - mean_x;
+        covariance += dx * (y - mean_y);
+        variance += dx * dx;
+    }
+    (covariance / variance) as f32
+}
+
+/// Nearest-rank percentile (0-100) of the daily counts on active days only;
+/// empty days are left out so quiet periods don't pull every value to 0.
+pub fn active_day_percentile(calendar: &ContributionCalendar, percentile: u32) -> u32 {
+    let mut counts: Vec<u32> = calendar.weeks.iter()
+        .flat_map(|w| &w.contribution_days)
+        .map(|d| d.contribution_count)
+        .filter(|&count| count > 0)
+        .collect();
+    if counts.is_empty() {
+        return 0;
+    }
+    counts.sort_unstable();
+    
+    let rank = (percentile as usize * counts.len()).div_ceil(100);
+    counts[rank.clamp(1, counts.len()) - 1]
+}
+
+/// Returns (current, longest) streaks of consecutive active days.
+/// The calendar is chronological, so once days after today are left out the
+/// current streak is the run of active days at the very end (zero if today is empty).
+pub fn compute_streaks(calendar: &ContributionCalendar) -> (u32, u32) {
+    let mut current = 0;
+    let mut longest = 0;
+    
+    for day in elapsed_days(calendar) {
+        if day.contribution_count > 0 {
+            current += 1;
+            longest = std::cmp::max(longest, current);
+        } else {
+            current = 0;
+        }
+    }
+    
+    (current, longest)
+}
+
+/// Longest run of consecutive days without contributions. Empty days at the
+/// start or end of the calendar count too, but not days after today; a fully
+/// active calendar gives 0.
+pub fn longest_gap(calendar: &ContributionCalendar) -> u32 {
+    let mut current = 0;
+    let mut longest = 0;
+    
+    for day in elapsed_days(calendar) {
+        if day.contribution_count == 0 {
+            current += 1;
+            longest = std::cmp::max(longest, current);
+        } else {
+            current = 0;
+        }
+    }
+    
+    longest
+}
+
+/// Whole months from `from` to `to`, e.g. for an account's age
+pub fn months_between(from: chrono::NaiveDate, to: chrono::NaiveDate) -> u32 {
+    let months = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32;
+    // The last month only counts once its day of the month is reached
+    let months = if to.day() < from.day() { months - 1 } else { months };
+    months.max(0) as u32
+}
+
+/// The year when `range` spans exactly one calendar year (Jan 1 to Dec 31)
+pub fn whole_year(range: DateRange) -> Option<i32> {
+    let year = range.from.year();
+    let is_whole = range.from.ordinal() == 1
+        && range.to.year() == year
+        && range.to.month() == 12
+        && range.to.day() == 31;
+    is_whole.then_some(year)
+}
+
+/// Sorts the days of each week and the weeks themselves by date, since the
+/// streak and gap math assumes chronological order. The ISO dates sort
+/// correctly as plain strings. Returns whether anything had to move.
+pub fn sort_chronologically(calendar: &mut ContributionCalendar) -> bool {
+    let mut moved = false;
+    for week in &mut calendar.weeks {
+        if !week.contribution_days.is_sorted_by(|a, b| a.date <= b.date) {
+            week.contribution_days.sort_by(|a, b| a.date.cmp(&b.date));
+            moved = true;
+        }
+    }
+    let first_date = |week: &ContributionWeek| week.contribution_days.first().map(|d| d.date.clone());
+    if !calendar.weeks.is_sorted_by_key(first_date) {
+        calendar.weeks.sort_by_key(first_date);
+        moved = true;
+    }
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // `past` ends today and is followed by `future` empty days still to come,
+    // the way GitHub pads the current week
+    fn calendar_ending_today(past: &[u32], future: usize) -> ContributionCalendar {
+        let today = chrono::Utc::now().date_naive();
+        let first = today - chrono::Days::new(past.len() as u64 - 1);
+        let counts = past.iter().copied().chain(std::iter::repeat_n(0, future));
+        let days: Vec<ContributionDay> = counts
+            .enumerate()
+            .map(|(offset, count)| ContributionDay {
+                date: (first + chrono::Days::new(offset as u64)).format("%Y-%m-%d").to_string(),
+                contribution_count: count,
+                color: String::new(),
+            })
+            .collect();
+        ContributionCalendar {
+            total_contributions: past.iter().sum(),
+            weeks: days.chunks(7).map(|week| ContributionWeek { contribution_days: week.to_vec() }).collect(),
+        }
+    }
+    
+    const PAST: [u32; 7] = [0, 0, 1, 2, 3, 4, 5];
+    
+    #[test]
+    fn future_days_keep_the_current_streak() {
+        assert_eq!(compute_streaks(&calendar_ending_today(&PAST, 4)), (5, 5));
+    }
+    
+    #[test]
+    fn future_days_are_not_a_gap() {
+        assert_eq!(longest_gap(&calendar_ending_today(&PAST, 4)), 2);
+        assert_eq!(longest_gap(&calendar_ending_today(&[3, 0, 0, 0, 1], 6)), 3);
+    }
+    
+    #[test]
+    fn future_days_leave_consistency_alone() {
+        let percent = consistency_percent(&calendar_ending_today(&PAST, 4));
+        assert!((percent - 500.0 / 7.0).abs() < 0.01, "{}", percent);
+    }
+    
+    #[test]
+    fn future_days_leave_the_trend_alone() {
+        let padded = trend_slope(&calendar_ending_today(&PAST, 4));
+        assert!((padded - 25.0 / 28.0).abs() < 1e-4, "{}", padded);
+        assert_eq!(padded, trend_slope(&calendar_ending_today(&PAST, 0)));
+    }
+    
+    #[test]
+    fn future_days_leave_the_std_dev_alone() {
+        assert_eq!(daily_std_dev(&calendar_ending_today(&PAST, 4)), daily_std_dev(&calendar_ending_today(&PAST, 0)));
+    }
+}
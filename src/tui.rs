@@ -0,0 +1,235 @@
+//! Interactive full-screen calendar (`--tui`).
+
+use std::io;
+
+use ratatui::crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseEventKind,
+};
+use ratatui::crossterm::execute;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::models::{ContributionCalendar, GitHubUser};
+use crate::render::{ansi16, ansi256, day_color, month_labels, period_label, ColorMode, RenderOptions};
+use crate::stats::{
+    average_per_active_day, compute_streaks, count_active_days, longest_gap,
+    max_contributions_per_day,
+};
+
+// Terminal columns per week: the square and a space
+const CELL_WIDTH: u16 = 2;
+// Room for the weekday labels left of the squares
+const LABEL_WIDTH: u16 = 4;
+
+// Selected day and what is currently on screen
+struct TuiState {
+    week: usize,
+    day: usize,
+    // First week visible in the grid
+    offset: usize,
+    show_stats: bool,
+    // Screen area of the squares, to map mouse positions back to days
+    grid: Rect,
+}
+
+/// Shows the calendar full screen until `q` is pressed
+pub fn run(user: &GitHubUser, calendar: &ContributionCalendar, options: &RenderOptions) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    // Start on the most recent day
+    let mut state = TuiState {
+        week: calendar.weeks.len().saturating_sub(1),
+        day: calendar.weeks.last().map_or(0, |w| w.contribution_days.len().saturating_sub(1)),
+        offset: 0,
+        show_stats: true,
+        grid: Rect::default(),
+    };
+    
+    let result = execute!(io::stdout(), EnableMouseCapture)
+        .and_then(|_| event_loop(&mut terminal, user, calendar, options, &mut state));
+    
+    // Always hand the terminal back in its normal state
+    let _ = execute!(io::stdout(), DisableMouseCapture);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    user: &GitHubUser,
+    calendar: &ContributionCalendar,
+    options: &RenderOptions,
+    state: &mut TuiState,
+) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| draw(frame, user, calendar, options, state))?;
+        
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('s') => state.show_stats = !state.show_stats,
+                KeyCode::Left | KeyCode::Char('h') => state.week = state.week.saturating_sub(1),
+                KeyCode::Right | KeyCode::Char('l') => state.week += 1,
+                KeyCode::Up | KeyCode::Char('k') => state.day = state.day.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => state.day += 1,
+                KeyCode::Home => state.week = 0,
+                KeyCode::End => state.week = usize::MAX,
+                _ => {}
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Moved | MouseEventKind::Down(_) => {
+                    if let Some((week, day)) = cell_at(calendar, state, mouse.column, mouse.row) {
+                        state.week = week;
+                        state.day = day;
+                    }
+                }
+                MouseEventKind::ScrollUp => state.week = state.week.saturating_sub(1),
+                MouseEventKind::ScrollDown => state.week += 1,
+                _ => {}
+            },
+            _ => {}
+        }
+        
+        // Keep the cursor on an existing day; the first and last weeks are partial
+        state.week = state.week.min(calendar.weeks.len().saturating_sub(1));
+        let days = calendar.weeks.get(state.week).map_or(0, |w| w.contribution_days.len());
+        state.day = state.day.min(days.saturating_sub(1));
+    }
+}
+
+// Week and day under a screen position, if there is a square there
+fn cell_at(calendar: &ContributionCalendar, state: &TuiState, column: u16, row: u16) -> Option<(usize, usize)> {
+    let grid = state.grid;
+    if column < grid.x || column >= grid.right() || row < grid.y || row >= grid.bottom() {
+        return None;
+    }
+    let week = state.offset + ((column - grid.x) / CELL_WIDTH) as usize;
+    let day = (row - grid.y) as usize;
+    calendar.weeks.get(week)?.contribution_days.get(day)?;
+    Some((week, day))
+}
+
+fn draw(frame: &mut Frame, user: &GitHubUser, calendar: &ContributionCalendar, options: &RenderOptions, state: &mut TuiState) {
+    let stats_height = if state.show_stats { 5 } else { 0 };
+    let [header_area, calendar_area, details_area, stats_area, _, help_area] = Layout::vertical([
+        Constraint::Length(2),
+        // Borders, month labels and seven days
+        Constraint::Length(10),
+        Constraint::Length(2),
+        Constraint::Length(stats_height),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    
+    let title = match &user.name {
+        Some(name) => format!("{} ({})", user.login, name),
+        None => user.login.clone(),
+    };
+    let header = vec![
+        Line::from(title.bold()).centered(),
+        Line::from(format!(
+            "GitHub Activity ({}): {} contributions",
+            period_label(options),
+            calendar.total_contributions
+        ))
+        .light_blue()
+        .centered(),
+    ];
+    frame.render_widget(Paragraph::new(header), header_area);
+    
+    let block = Block::bordered().title(" Contributions ").border_style(Style::new().blue());
+    let inner = block.inner(calendar_area);
+    frame.render_widget(block, calendar_area);
+    draw_grid(frame, calendar, options, state, inner);
+    
+    let details = match calendar.weeks.get(state.week).and_then(|w| w.contribution_days.get(state.day)) {
+        Some(day) => {
+            let weekday = chrono::NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")
+                .map(|date| date.format(" (%A)").to_string())
+                .unwrap_or_default();
+            let unit = if day.contribution_count == 1 { "contribution" } else { "contributions" };
+            format!("{}{}: {} {}", day.date, weekday, day.contribution_count, unit)
+        }
+        None => "No contributions in this period".to_string(),
+    };
+    frame.render_widget(Paragraph::new(Line::from(details.light_cyan()).centered()), details_area);
+    
+    if state.show_stats {
+        draw_statistics(frame, calendar, stats_area);
+    }
+    
+    let help = "←↓↑→/hjkl move  Home/End jump  mouse select  s statistics  q quit";
+    frame.render_widget(Paragraph::new(Line::from(help.dark_gray()).centered()), help_area);
+}
+
+fn draw_grid(frame: &mut Frame, calendar: &ContributionCalendar, options: &RenderOptions, state: &mut TuiState, area: Rect) {
+    let visible = (area.width.saturating_sub(LABEL_WIDTH) / CELL_WIDTH) as usize;
+    // Scroll just enough to keep the selected week in view
+    if state.week < state.offset {
+        state.offset = state.week;
+    } else if visible > 0 && state.week >= state.offset + visible {
+        state.offset = state.week + 1 - visible;
+    }
+    let end = calendar.weeks.len().min(state.offset + visible);
+    let weeks = &calendar.weeks[state.offset.min(end)..end];
+    
+    state.grid = Rect {
+        x: area.x + LABEL_WIDTH,
+        y: area.y + 1,
+        width: weeks.len() as u16 * CELL_WIDTH,
+        height: area.height.saturating_sub(1).min(7),
+    };
+    
+    let label_padding = " ".repeat(LABEL_WIDTH as usize);
+    let mut lines = vec![Line::from(format!("{}{}", label_padding, month_labels(weeks, CELL_WIDTH as usize)).blue())];
+    let weekdays = ["Mon", "Wed", "Fri"];
+    for row in 0..7 {
+        let label = if row % 2 == 1 { weekdays[row / 2] } else { "" };
+        let mut spans = vec![Span::styled(format!("{:<3} ", label), Style::new().blue())];
+        for (idx, week) in weeks.iter().enumerate() {
+            let Some(day) = week.contribution_days.get(row) else {
+                spans.push(Span::raw("  "));
+                continue;
+            };
+            let color = match (day_color(day, options), options.color_mode) {
+                (rgb, ColorMode::Ansi256) => Color::Indexed(ansi256(rgb)),
+                (rgb, ColorMode::Basic) => Color::Indexed(ansi16(rgb)),
+                ((r, g, b), _) => Color::Rgb(r, g, b),
+            };
+            let mut style = Style::new().fg(color);
+            if state.offset + idx == state.week && row == state.day {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            let glyph = match options.empty_char {
+                Some(empty) if day.contribution_count == 0 => empty.to_string(),
+                _ => "■".to_string(),
+            };
+            spans.push(Span::styled(glyph, style));
+            spans.push(Span::raw(" "));
+        }
+        lines.push(Line::from(spans));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_statistics(frame: &mut Frame, calendar: &ContributionCalendar, area: Rect) {
+    let (current_streak, longest_streak) = compute_streaks(calendar);
+    let lines = vec![
+        Line::from(format!(
+            "Active Days: {}  |  Max/Day: {}  |  Avg/Active Day: {:.1}",
+            count_active_days(calendar),
+            max_contributions_per_day(calendar),
+            average_per_active_day(calendar)
+        )),
+        Line::from(format!(
+            "Current Streak: {}  |  Longest Streak: {}",
+            current_streak, longest_streak
+        )),
+        Line::from(format!("Longest Gap: {} days", longest_gap(calendar))),
+    ];
+    let block = Block::bordered().title(" Statistics ").border_style(Style::new().blue());
+    frame.render_widget(Paragraph::new(lines).light_cyan().centered().block(block), area);
+}
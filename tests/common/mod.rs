@@ -0,0 +1,76 @@
+//! A minimal HTTP server standing in for the GitHub API in tests.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+use gitstat::Api;
+use tokio::sync::Semaphore;
+
+/// Status line, extra header lines (each ending in "\r\n") and body
+pub type Reply = (&'static str, &'static str, &'static str);
+
+/// Answers every request with `handler(path)` on a free port, recording the
+/// Authorization header of each request. Returns the base URL to point an
+/// `Api` at and the recorded headers.
+pub fn serve(handler: fn(&str) -> Reply) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&seen);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut authorization = None;
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("authorization") {
+                        authorization = Some(value.trim().to_string());
+                    } else if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            recorded.lock().unwrap().push(authorization);
+
+            let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+            let (status, extra, body) = handler(path);
+            // The client may have given up already, e.g. after a timeout
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                extra,
+                body.len(),
+                body
+            );
+        }
+    });
+    (base_url, seen)
+}
+
+/// An `Api` on `base_url` that never retries
+pub fn api(base_url: String) -> Api {
+    Api {
+        client: reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(1))
+            .build()
+            .unwrap(),
+        base_url,
+        retries: 0,
+        timeout_secs: 1,
+        limiter: Semaphore::new(1),
+        delay_ms: 0,
+    }
+}
@@ -0,0 +1,97 @@
+//! The token must never show up in an error, its Debug output or a log line.
+
+mod common;
+
+use std::sync::Mutex;
+
+use common::{api, serve, Reply};
+use gitstat::api::{validate_token, Api};
+use gitstat::{get_user_contributions_real, GitStatError};
+
+const TOKEN: &str = "ghp_doNotLeakThisToken0123456789";
+
+// Every log line written by the crate while the tests run
+static LOGGED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+impl log::Log for Capture {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        LOGGED.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture;
+
+// Each failure is picked by the path prefix the Api's base URL carries
+fn failures(path: &str) -> Reply {
+    match path.split('/').nth(1).unwrap_or_default() {
+        "unauthorized" => ("401 Unauthorized", "", r#"{"message":"Bad credentials"}"#),
+        "limited" => ("429 Too Many Requests", "Retry-After: 30\r\n", "{}"),
+        "broken" => ("500 Internal Server Error", "", "{}"),
+        "garbled" => ("200 OK", "", "not json"),
+        "missing" => ("200 OK", "", r#"{"data":{"user":null}}"#),
+        "invalid" => ("200 OK", "", r#"{"errors":[{"message":"Something went wrong"}]}"#),
+        "slow" => {
+            std::thread::sleep(std::time::Duration::from_secs(2));
+            ("200 OK", "", "{}")
+        }
+        _ => ("404 Not Found", "", "{}"),
+    }
+}
+
+fn api_at(base_url: &str, prefix: &str) -> Api {
+    api(format!("{}/{}", base_url, prefix))
+}
+
+fn assert_redacted(error: &GitStatError) {
+    assert!(!error.to_string().contains(TOKEN), "token in Display: {}", error);
+    assert!(!format!("{:?}", error).contains(TOKEN), "token in Debug: {:?}", error);
+}
+
+#[tokio::test]
+async fn errors_and_logs_never_contain_the_token() {
+    log::set_logger(&CAPTURE).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    let (base_url, seen) = serve(failures);
+
+    let mut errors = Vec::new();
+    for (prefix, expected) in [
+        ("limited", "RateLimited"),
+        ("broken", "Http"),
+        ("garbled", "Decode"),
+        ("missing", "NotFound"),
+        ("invalid", "GraphQl"),
+        ("slow", "Timeout"),
+    ] {
+        let error = get_user_contributions_real(&api_at(&base_url, prefix), "alice", TOKEN, None)
+            .await
+            .unwrap_err();
+        assert!(format!("{:?}", error).starts_with(expected), "{} gave {:?}", prefix, error);
+        errors.push(error);
+    }
+    errors.push(validate_token(&api_at(&base_url, "unauthorized"), TOKEN).await.unwrap_err());
+    // Nothing listens on port 1, so the connection itself fails
+    let refused = get_user_contributions_real(&api("http://127.0.0.1:1".to_string()), "alice", TOKEN, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(refused, GitStatError::Network(_)), "{:?}", refused);
+    errors.push(refused);
+    errors.push(GitStatError::StatsPending("alice/repo".to_string()));
+
+    assert!(matches!(errors[6], GitStatError::Unauthorized(_)));
+    for error in &errors {
+        assert_redacted(error);
+    }
+    // The token did go out, but only in the Authorization header
+    assert!(seen.lock().unwrap().iter().all(|header| header.as_deref() == Some(&format!("Bearer {}", TOKEN)[..])));
+    let logged = LOGGED.lock().unwrap();
+    assert!(logged.iter().any(|line| line.contains("authorization: [redacted]")));
+    assert!(logged.iter().all(|line| !line.contains(TOKEN)), "token logged: {:?}", logged);
+}
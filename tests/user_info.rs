@@ -0,0 +1,67 @@
+//! `get_user_info` against a local mock of the users endpoint.
+
+mod common;
+
+use common::{api, serve, Reply};
+use gitstat::{get_user_info, GitStatError};
+
+fn users(path: &str) -> Reply {
+    let body = r#"{"message":"nope"}"#;
+    match path {
+        "/users/alice" => ("200 OK", "", r#"{"login":"alice","name":null,"public_repos":1}"#),
+        "/users/revoked" => ("401 Unauthorized", "", body),
+        "/users/hidden" => ("403 Forbidden", "", body),
+        "/users/busy" => ("403 Forbidden", "Retry-After: 90\r\n", body),
+        _ => ("404 Not Found", "", body),
+    }
+}
+
+#[tokio::test]
+async fn sends_the_token() {
+    let (base_url, seen) = serve(users);
+    let user = get_user_info(&api(base_url), "alice", "secret").await.unwrap();
+    assert_eq!(user.login, "alice");
+    assert_eq!(seen.lock().unwrap().as_slice(), [Some("Bearer secret".to_string())]);
+}
+
+#[tokio::test]
+async fn anonymous_requests_have_no_authorization() {
+    let (base_url, seen) = serve(users);
+    get_user_info(&api(base_url), "alice", "").await.unwrap();
+    assert_eq!(seen.lock().unwrap().as_slice(), [None]);
+}
+
+#[tokio::test]
+async fn rejected_token_is_unauthorized() {
+    let (base_url, _) = serve(users);
+    let error = get_user_info(&api(base_url), "revoked", "secret").await.unwrap_err();
+    assert!(matches!(error, GitStatError::Unauthorized(ref message) if message.contains("refused the credentials")));
+}
+
+#[tokio::test]
+async fn forbidden_with_token_is_unauthorized() {
+    let (base_url, _) = serve(users);
+    let error = get_user_info(&api(base_url), "hidden", "secret").await.unwrap_err();
+    assert!(matches!(error, GitStatError::Unauthorized(ref message) if message.contains("forbidden (HTTP 403)")));
+}
+
+#[tokio::test]
+async fn anonymous_forbidden_keeps_the_status() {
+    let (base_url, _) = serve(users);
+    let error = get_user_info(&api(base_url), "hidden", "").await.unwrap_err();
+    assert!(matches!(error, GitStatError::Http(status) if status == reqwest::StatusCode::FORBIDDEN));
+}
+
+#[tokio::test]
+async fn retry_after_is_a_secondary_rate_limit() {
+    let (base_url, _) = serve(users);
+    let error = get_user_info(&api(base_url), "busy", "secret").await.unwrap_err();
+    assert!(matches!(error, GitStatError::RateLimited { reset_in_minutes: Some(2) }));
+}
+
+#[tokio::test]
+async fn missing_user_is_not_found() {
+    let (base_url, _) = serve(users);
+    let error = get_user_info(&api(base_url), "nobody", "secret").await.unwrap_err();
+    assert!(matches!(error, GitStatError::NotFound { kind: "User", .. }));
+}